@@ -20,13 +20,13 @@ print("""pub const REVERSE_TABLE: [u32; 256] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
 ];""")
 
-print("pub const REVERSE_PAIR_TABLE: [u16; 65535] = [", end="")
+print("pub static REVERSE_PAIR_TABLE: [u16; 65536] = [", end="")
 
 indexes = {k: v for k, v in zip(string.ascii_uppercase + string.ascii_lowercase + string.digits + "+" + "/", range(64))}
 pairs = list(itertools.product(string.ascii_uppercase + string.ascii_lowercase + string.digits + "+" + "/", repeat=2))
 pairs_indexes = {ord(pair[0]) | ord(pair[1]) << 8: indexes[pair[0]] << 6 | indexes[pair[1]] for pair in pairs}
 
-for i in range(65535):
+for i in range(65536):
     print(f"{pairs_indexes.get(i, 0)},", end="")
 
 print("];")
@@ -0,0 +1,150 @@
+/// Symbol set with a reverse lookup table, both built at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: &'static [u8],
+    reverse: [u8; 256],
+}
+
+const INVALID: u8 = 0xff;
+
+impl Alphabet {
+    /// Creates an alphabet from a list of unique ASCII symbols.
+    ///
+    /// Panics (at compile time when used in a `const`) if a symbol is repeated, is not ASCII,
+    /// or the list holds more than 255 symbols.
+    pub const fn new(symbols: &'static [u8]) -> Self {
+        assert!(!symbols.is_empty() && symbols.len() < 256);
+
+        let mut reverse = [INVALID; 256];
+        let mut i = 0;
+        while i < symbols.len() {
+            let symbol = symbols[i] as usize;
+            assert!(symbol < 0x80, "alphabet symbols must be ASCII");
+            assert!(
+                reverse[symbol] == INVALID,
+                "alphabet symbols must be unique"
+            );
+            reverse[symbol] = i as u8;
+            i += 1;
+        }
+
+        Self { symbols, reverse }
+    }
+
+    /// Makes `alias` decode to the same value as `symbol`.
+    ///
+    /// Aliases are only used when decoding, encoding always produces `symbol`.
+    pub const fn alias(mut self, alias: u8, symbol: u8) -> Self {
+        assert!(alias < 0x80 && self.reverse[alias as usize] == INVALID);
+        assert!(self.reverse[symbol as usize] != INVALID);
+        self.reverse[alias as usize] = self.reverse[symbol as usize];
+        self
+    }
+
+    /// Number of symbols, which is also the radix of the encoding.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Symbols in the order of their values.
+    pub const fn symbols(&self) -> &'static [u8] {
+        self.symbols
+    }
+
+    /// Returns the symbol encoding `value`.
+    pub const fn symbol(&self, value: u8) -> u8 {
+        self.symbols[value as usize]
+    }
+
+    /// Returns the value of `symbol`, or `None` if it is not part of the alphabet.
+    pub const fn value(&self, symbol: u8) -> Option<u8> {
+        match self.reverse[symbol as usize] {
+            INVALID => None,
+            value => Some(value),
+        }
+    }
+
+    /// Returns the offset of the first byte in `data` that is not part of the alphabet.
+    pub fn find_invalid(&self, data: &[u8]) -> Option<usize> {
+        find_chunked(data, |b| self.reverse[b as usize] == INVALID)
+    }
+
+    /// Returns `true` if the symbols are in ascending ASCII order, so encoded values sort
+    /// the same way as the values themselves.
+    pub const fn is_sorted(&self) -> bool {
+        let mut i = 1;
+        while i < self.symbols.len() {
+            if self.symbols[i - 1] >= self.symbols[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+/// Returns a bitmask of the bytes in `chunk`, at most 64, that `class` matches.
+///
+/// Classifying a whole chunk without branching lets the compiler vectorize it, positions
+/// are then found with bit scans.
+pub(crate) fn chunk_mask(chunk: &[u8], class: impl Fn(u8) -> bool) -> u64 {
+    chunk
+        .iter()
+        .enumerate()
+        .fold(0, |mask, (i, &b)| mask | (class(b) as u64) << i)
+}
+
+/// Returns the offset of the first byte in `data` that `class` matches, 64 bytes at a time.
+pub(crate) fn find_chunked(data: &[u8], class: impl Fn(u8) -> bool) -> Option<usize> {
+    data.chunks(64).enumerate().find_map(|(i, chunk)| {
+        let mask = chunk_mask(chunk, &class);
+        (mask != 0).then(|| i * 64 + mask.trailing_zeros() as usize)
+    })
+}
+
+/// Standard Base64 alphabet from RFC 4648.
+pub const STANDARD: Alphabet =
+    Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+
+/// URL and filename safe Base64 alphabet from RFC 4648.
+pub const URL_SAFE: Alphabet =
+    Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+#[cfg(test)]
+mod tests {
+    use crate::alphabet::{self, Alphabet};
+
+    #[test]
+    fn standard_matches_table() {
+        assert_eq!(alphabet::STANDARD.symbols(), &crate::tables::TABLE);
+    }
+
+    #[test]
+    fn value() {
+        assert_eq!(alphabet::URL_SAFE.value(b'_'), Some(63));
+        assert_eq!(alphabet::URL_SAFE.value(b'/'), None);
+    }
+
+    #[test]
+    fn alias() {
+        let alphabet = Alphabet::new(b"01").alias(b'o', b'0');
+        assert_eq!(alphabet.value(b'o'), Some(0));
+    }
+
+    #[test]
+    fn find_invalid() {
+        let mut data = [b'A'; 130];
+        assert_eq!(alphabet::STANDARD.find_invalid(&data), None);
+        data[129] = b'-';
+        data[70] = b'=';
+        assert_eq!(alphabet::STANDARD.find_invalid(&data), Some(70));
+        assert_eq!(alphabet::STANDARD.find_invalid(&data[..64]), None);
+    }
+
+    #[test]
+    fn is_sorted() {
+        assert!(!alphabet::STANDARD.is_sorted());
+        assert!(Alphabet::new(b"0123456789").is_sorted());
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::alphabet;
+use crate::tables::*;
+use crate::{encode_to_slice, encoded_len, DecodeError};
+
+/// Fixed-size string of Base64 returned by [`encode_array`], stored inline.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayString<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> ArrayString<N> {
+    pub fn as_str(&self) -> &str {
+        unsafe { std::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize> PartialEq<str> for ArrayString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Encodes a fixed-size array as padded standard Base64 without allocating.
+///
+/// The output length `M` must be `encoded_len(N)`, which is checked at compile time and is
+/// usually inferred from the type annotation.
+///
+/// ```
+/// use faster_base64::{encode_array, ArrayString};
+///
+/// let key = [0xffu8; 16];
+/// let encoded: ArrayString<24> = encode_array(&key);
+/// assert_eq!(encoded, "/////////////////////w==");
+/// ```
+pub fn encode_array<const N: usize, const M: usize>(data: &[u8; N]) -> ArrayString<M> {
+    const { assert!(M == encoded_len(N), "M must be encoded_len(N)") };
+
+    let mut bytes = [0u8; M];
+
+    // N is known at compile time, so this loop is fully unrolled for small arrays
+    for i in 0..N / 3 {
+        let value = (data[i * 3] as usize) << 16
+            | (data[i * 3 + 1] as usize) << 8
+            | data[i * 3 + 2] as usize;
+        let symbols = PAIR_TABLE[value >> 12 & 0xfff] | PAIR_TABLE[value & 0xfff] << 16;
+        bytes[i * 4..i * 4 + 4].copy_from_slice(&symbols.to_le_bytes());
+    }
+
+    if !N.is_multiple_of(3) {
+        encode_to_slice(&data[N / 3 * 3..], &mut bytes[N / 3 * 4..]);
+    }
+
+    ArrayString { bytes }
+}
+
+/// Decodes padded standard Base64 of exactly `N` bytes into an array.
+///
+/// Any input that does not decode to exactly `N` bytes is rejected.
+///
+/// ```
+/// let digest: [u8; 5] = faster_base64::decode_exact(b"SGVsbG8=").unwrap();
+/// assert_eq!(&digest, b"Hello");
+/// ```
+pub fn decode_exact<const N: usize>(data: &[u8]) -> Result<[u8; N], DecodeError> {
+    if data.len() != encoded_len(N) {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    let symbols_len = N / 3 * 4 + (N % 3 * 4).div_ceil(3);
+    let (symbols, padding) = data.split_at(symbols_len);
+
+    if let Some(i) = alphabet::STANDARD.find_invalid(symbols) {
+        return Err(DecodeError::InvalidByte(i, symbols[i]));
+    }
+    if let Some(i) = padding.iter().position(|&b| b != b'=') {
+        return Err(DecodeError::InvalidByte(symbols_len + i, padding[i]));
+    }
+
+    let mut out = [0u8; N];
+
+    for i in 0..N / 3 {
+        let first = u16::from_le_bytes([symbols[i * 4], symbols[i * 4 + 1]]);
+        let second = u16::from_le_bytes([symbols[i * 4 + 2], symbols[i * 4 + 3]]);
+        let value = (REVERSE_PAIR_TABLE[first as usize] as u32) << 12
+            | REVERSE_PAIR_TABLE[second as usize] as u32;
+        out[i * 3..i * 3 + 3].copy_from_slice(&value.to_be_bytes()[1..]);
+    }
+
+    let tail = &symbols[N / 3 * 4..];
+    if !tail.is_empty() {
+        let value = tail
+            .iter()
+            .fold(0, |value, &b| value << 6 | REVERSE_TABLE[b as usize])
+            << (6 * (4 - tail.len()));
+        out[N / 3 * 3..].copy_from_slice(&value.to_be_bytes()[1..tail.len()]);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode_exact, encode_array, ArrayString, DecodeError};
+
+    #[test]
+    fn encode_array_short() {
+        let encoded: ArrayString<8> = encode_array(b"Hello");
+        assert_eq!(encoded, "SGVsbG8=");
+        let encoded: ArrayString<4> = encode_array(b"Hel");
+        assert_eq!(encoded, "SGVs");
+    }
+
+    #[test]
+    fn encode_array_large() {
+        let data = [7u8; 64];
+        let encoded: ArrayString<88> = encode_array(&data);
+        assert_eq!(encoded, *crate::encode(&data));
+    }
+
+    #[test]
+    fn decode_exact_short() {
+        assert_eq!(decode_exact::<5>(b"SGVsbG8="), Ok(*b"Hello"));
+        assert_eq!(decode_exact::<4>(b"SGVsbA=="), Ok(*b"Hell"));
+        assert_eq!(decode_exact::<3>(b"SGVs"), Ok(*b"Hel"));
+        assert_eq!(decode_exact::<0>(b""), Ok([]));
+    }
+
+    #[test]
+    fn decode_exact_roundtrip() {
+        let data: [u8; 32] = std::array::from_fn(|i| (i * 37) as u8);
+        let encoded: ArrayString<44> = encode_array(&data);
+        assert_eq!(decode_exact::<32>(encoded.as_bytes()), Ok(data));
+    }
+
+    #[test]
+    fn decode_exact_length_mismatch() {
+        assert_eq!(
+            decode_exact::<4>(b"SGVsbG8="),
+            Err(DecodeError::InvalidByte(6, b'8'))
+        );
+        assert_eq!(
+            decode_exact::<6>(b"SGVsbG8="),
+            Err(DecodeError::InvalidByte(7, b'='))
+        );
+        assert_eq!(
+            decode_exact::<7>(b"SGVsbG8="),
+            Err(DecodeError::InvalidLength(8))
+        );
+    }
+
+    #[test]
+    fn decode_exact_invalid() {
+        assert_eq!(
+            decode_exact::<5>(b"SGV*bG8="),
+            Err(DecodeError::InvalidByte(3, b'*'))
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Base64 encode or decode files, compatible with GNU `base64`.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use faster_base64::engine::{self, Engine};
+use faster_base64::stream::{DecoderReader, EncoderWriter};
+
+const USAGE: &str = "\
+Usage: fb64 [OPTION]... [FILE]
+Base64 encode or decode FILE, or standard input, to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -d, --decode          decode data
+  -i, --ignore-garbage  when decoding, ignore non-alphabet characters
+  -w, --wrap=COLS       wrap encoded lines after COLS character (default 76).
+                          Use 0 to disable line wrapping
+      --url             use the URL and filename safe alphabet
+      --no-pad          omit padding when encoding, reject it when decoding
+      --help            display this help and exit
+      --version         output version information and exit
+";
+
+const VERSION: &str = concat!("fb64 (faster-base64) ", env!("CARGO_PKG_VERSION"));
+
+/// Long options without their leading `--`.
+const LONG_OPTIONS: &[&str] = &[
+    "decode",
+    "ignore-garbage",
+    "wrap",
+    "url",
+    "no-pad",
+    "help",
+    "version",
+];
+
+const BUF_SIZE: usize = 3 * 64 * 1024;
+
+struct Options {
+    decode: bool,
+    ignore_garbage: bool,
+    wrap: usize,
+    url: bool,
+    no_pad: bool,
+    file: Option<String>,
+}
+
+enum Action {
+    Run(Options),
+    Help,
+    Version,
+}
+
+enum ArgsError {
+    /// Misuse of the command line, reported with a pointer to `--help`.
+    Usage(String),
+    /// A wrap size that is not a number, reported on its own like GNU `base64`.
+    InvalidWrap(String),
+}
+
+fn parse_wrap(value: &str) -> Result<usize, ArgsError> {
+    value
+        .parse()
+        .map_err(|_| ArgsError::InvalidWrap(format!("invalid wrap size: '{}'", value)))
+}
+
+/// Resolves `--name` or `--name=value`, where `name` can be an unambiguous prefix like in
+/// GNU `getopt_long`.
+fn long_option(arg: &str) -> Result<(&'static str, Option<&str>), ArgsError> {
+    let (name, value) = match arg[2..].split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (&arg[2..], None),
+    };
+
+    if let Some(&option) = LONG_OPTIONS.iter().find(|&&option| option == name) {
+        return Ok((option, value));
+    }
+    let matches: Vec<&str> = LONG_OPTIONS
+        .iter()
+        .copied()
+        .filter(|option| option.starts_with(name))
+        .collect();
+    match matches[..] {
+        [option] => Ok((option, value)),
+        [] => Err(ArgsError::Usage(format!("unrecognized option '{}'", arg))),
+        _ => {
+            let possibilities: Vec<String> = matches
+                .iter()
+                .map(|option| format!("'--{}'", option))
+                .collect();
+            Err(ArgsError::Usage(format!(
+                "option '{}' is ambiguous; possibilities: {}",
+                arg,
+                possibilities.join(" ")
+            )))
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Action, ArgsError> {
+    let mut options = Options {
+        decode: false,
+        ignore_garbage: false,
+        wrap: 76,
+        url: false,
+        no_pad: false,
+        file: None,
+    };
+
+    let mut operands = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" => {
+                operands.extend(args.by_ref());
+            }
+            "-" => operands.push(arg),
+            _ if arg.starts_with("--") => match long_option(&arg)? {
+                ("wrap", value) => {
+                    let value = match value {
+                        Some(value) => value.to_string(),
+                        None => args.next().ok_or_else(|| {
+                            ArgsError::Usage("option '--wrap' requires an argument".to_string())
+                        })?,
+                    };
+                    options.wrap = parse_wrap(&value)?;
+                }
+                (option, Some(_)) => {
+                    return Err(ArgsError::Usage(format!(
+                        "option '--{}' doesn't allow an argument",
+                        option
+                    )));
+                }
+                ("decode", None) => options.decode = true,
+                ("ignore-garbage", None) => options.ignore_garbage = true,
+                ("url", None) => options.url = true,
+                ("no-pad", None) => options.no_pad = true,
+                ("help", None) => return Ok(Action::Help),
+                ("version", None) => return Ok(Action::Version),
+                (option, None) => unreachable!("unhandled option '--{}'", option),
+            },
+            _ if arg.starts_with('-') => {
+                for (i, flag) in arg.char_indices().skip(1) {
+                    match flag {
+                        'd' => options.decode = true,
+                        'i' => options.ignore_garbage = true,
+                        'w' => {
+                            let value = match &arg[i + 1..] {
+                                "" => args.next().ok_or_else(|| {
+                                    ArgsError::Usage(
+                                        "option requires an argument -- 'w'".to_string(),
+                                    )
+                                })?,
+                                value => value.to_string(),
+                            };
+                            options.wrap = parse_wrap(&value)?;
+                            break;
+                        }
+                        _ => return Err(ArgsError::Usage(format!("invalid option -- '{}'", flag))),
+                    }
+                }
+            }
+            _ => operands.push(arg),
+        }
+    }
+
+    if operands.len() > 1 {
+        return Err(ArgsError::Usage(format!("extra operand '{}'", operands[1])));
+    }
+    options.file = operands.pop().filter(|file| file != "-");
+
+    Ok(Action::Run(options))
+}
+
+/// Inserts a line break after every `cols` bytes and at the end of non-empty output.
+struct LineWrapper<W: Write> {
+    inner: W,
+    cols: usize,
+    col: usize,
+}
+
+impl<W: Write> LineWrapper<W> {
+    fn finish(mut self) -> io::Result<W> {
+        if self.cols > 0 && self.col > 0 {
+            self.inner.write_all(b"\n")?;
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for LineWrapper<W> {
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len();
+        if self.cols == 0 {
+            self.inner.write_all(buf)?;
+            return Ok(len);
+        }
+
+        while !buf.is_empty() {
+            if self.col == self.cols {
+                self.inner.write_all(b"\n")?;
+                self.col = 0;
+            }
+            let take = buf.len().min(self.cols - self.col);
+            self.inner.write_all(&buf[..take])?;
+            self.col += take;
+            buf = &buf[take..];
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let engine: &'static Engine = match (options.url, options.no_pad) {
+        (false, false) => &engine::STANDARD,
+        (false, true) => &engine::STANDARD_NO_PAD,
+        (true, false) => &engine::URL_SAFE,
+        (true, true) => &engine::URL_SAFE_NO_PAD,
+    };
+
+    let input: Box<dyn Read> = match &options.file {
+        Some(path) => Box::new(File::open(path).map_err(|err| {
+            io::Error::new(err.kind(), format!("{}: {}", path, strip_os_error(&err)))
+        })?),
+        None => Box::new(io::stdin().lock()),
+    };
+    let mut input = BufReader::with_capacity(BUF_SIZE, input);
+    let mut output = BufWriter::with_capacity(BUF_SIZE, io::stdout().lock());
+
+    if options.decode {
+        let mut decoder =
+            DecoderReader::with_engine(input, engine).ignore_garbage(options.ignore_garbage);
+        let copied = io::copy(&mut decoder, &mut output);
+        // Like GNU `base64`, the data decoded before invalid input is still written
+        output.flush()?;
+        copied.map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => io::Error::new(err.kind(), "invalid input"),
+            _ => err,
+        })?;
+    } else {
+        let wrapper = LineWrapper {
+            inner: &mut output,
+            cols: options.wrap,
+            col: 0,
+        };
+        let mut encoder = EncoderWriter::with_engine(wrapper, engine);
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.finish()?;
+    }
+
+    output.flush()
+}
+
+/// Formats an I/O error like coreutils, without the " (os error N)" suffix.
+fn strip_os_error(err: &io::Error) -> String {
+    let message = err.to_string();
+    match message.find(" (os error") {
+        Some(end) => message[..end].to_string(),
+        None => message,
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Action::Run(options)) => options,
+        Ok(Action::Help) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Action::Version) => {
+            println!("{}", VERSION);
+            return ExitCode::SUCCESS;
+        }
+        Err(ArgsError::Usage(message)) => {
+            eprintln!("fb64: {}", message);
+            eprintln!("Try 'fb64 --help' for more information.");
+            return ExitCode::FAILURE;
+        }
+        Err(ArgsError::InvalidWrap(message)) => {
+            eprintln!("fb64: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("fb64: {}", strip_os_error(&err));
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Encoding and decoding of `bytes` buffers.
+//!
+//! ```
+//! use bytes::BytesMut;
+//!
+//! let buf = BytesMut::from(&b"SGVsbG8="[..]);
+//! let decoded = faster_base64::bytes::decode_bytes_mut(buf).unwrap();
+//! assert_eq!(&decoded[..], b"Hello");
+//! ```
+
+use std::convert::Infallible;
+
+use ::bytes::{Buf, BufMut, BytesMut};
+
+use crate::engine;
+use crate::{encoded_len, DecodeError, Encoder};
+
+/// Decodes padded standard Base64 in-place and truncates `buf` to the decoded data.
+pub fn decode_bytes_mut(mut buf: BytesMut) -> Result<BytesMut, DecodeError> {
+    let len = engine::STANDARD.decode(&mut buf)?.len();
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// Encodes `data` as padded standard Base64 and appends it to `buf`.
+///
+/// Panics if `buf` does not have room for the encoded data.
+pub fn encode_into_buf(data: &[u8], buf: &mut impl BufMut) {
+    let Ok(()) = engine::STANDARD.encode_chunks(data, |encoded| {
+        buf.put_slice(encoded.as_bytes());
+        Ok::<_, Infallible>(())
+    });
+}
+
+/// Encodes the remaining data of `buf` as padded standard Base64.
+///
+/// Non-contiguous chunks are encoded one after another, without copying them together.
+pub fn encode_buf(mut buf: impl Buf) -> String {
+    let mut out = vec![0u8; encoded_len(buf.remaining())];
+    let mut encoder = Encoder::new();
+    let mut produced = 0;
+
+    while buf.has_remaining() {
+        let chunk = buf.chunk();
+        let (consumed, len) = encoder.update(chunk, &mut out[produced..]);
+        produced += len;
+        buf.advance(consumed);
+    }
+    produced += encoder.finish(&mut out[produced..]);
+    out.truncate(produced);
+
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::bytes::{Buf, BytesMut};
+
+    use crate::bytes::{decode_bytes_mut, encode_buf, encode_into_buf};
+    use crate::DecodeError;
+
+    #[test]
+    fn decode_bytes_mut_in_place() {
+        let buf = BytesMut::from(&b"SGVsbG8="[..]);
+        let ptr = buf.as_ptr();
+        let decoded = decode_bytes_mut(buf).unwrap();
+        assert_eq!(&decoded[..], b"Hello");
+        assert_eq!(decoded.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn decode_bytes_mut_invalid() {
+        assert_eq!(
+            decode_bytes_mut(BytesMut::from(&b"SGVs*G8="[..])),
+            Err(DecodeError::InvalidByte(4, b'*'))
+        );
+    }
+
+    #[test]
+    fn encode_into_buf_long() {
+        let data = crate::tests::data(769);
+        // Tails of 0, 1 and 2 bytes around the chunks encoded on the stack
+        for len in [0, 1, 2, 767, 768, 769] {
+            let mut buf = BytesMut::new();
+            encode_into_buf(&data[..len], &mut buf);
+            assert_eq!(&buf[..], crate::encode(&data[..len]).as_bytes());
+        }
+    }
+
+    #[test]
+    fn encode_buf_chained() {
+        // Chunks that end 1 and 2 bytes into a block, and one within a single block
+        let data = crate::tests::data(20);
+        let (first, rest) = data.split_at(4);
+        let (second, rest) = rest.split_at(4);
+        let (third, fourth) = rest.split_at(1);
+        let buf = first.chain(second).chain(third).chain(fourth);
+        assert_eq!(encode_buf(buf), crate::encode(&data));
+    }
+}
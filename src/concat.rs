@@ -0,0 +1,155 @@
+use crate::{
+    alphabet, decode_unpadded, decoded_len, encode, encoded_len, padded_symbols_len, DecodeError,
+};
+
+/// Validates padded standard Base64 and returns the number of symbols before the padding.
+fn symbols_len(data: &[u8]) -> Result<usize, DecodeError> {
+    let len = padded_symbols_len(data)?;
+    if let Some(i) = alphabet::STANDARD.find_invalid(&data[..len]) {
+        return Err(DecodeError::InvalidByte(i, data[i]));
+    }
+    Ok(len)
+}
+
+/// Concatenates two padded standard Base64 strings into the encoding of their concatenated
+/// data.
+///
+/// The whole blocks of `a` are copied as they are, only its last partial block is decoded.
+/// If `a` has no padding, `b` is copied too. Otherwise the block boundaries of `b` shift,
+/// so `b` is re-encoded together with the 1–2 bytes of `a`'s last block. Errors in `b` have
+/// offsets as if both were one string.
+///
+/// ```
+/// let joined = faster_base64::concat_encoded("SGVsbG8=", "LCB3b3JsZA==").unwrap();
+/// assert_eq!(joined, faster_base64::encode(b"Hello, world"));
+/// ```
+pub fn concat_encoded(a: &str, b: &str) -> Result<String, DecodeError> {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let a_symbols = symbols_len(a)?;
+    let b_symbols = symbols_len(b).map_err(|err| err.offset_by(a.len()))?;
+
+    let tail_len = a_symbols % 4;
+    let head = &a[..a_symbols - tail_len];
+
+    let mut out = String::with_capacity(a.len() + encoded_len(b.len() / 4 * 3 + 2));
+    out.push_str(unsafe { std::str::from_utf8_unchecked(head) });
+
+    if tail_len == 0 {
+        out.push_str(unsafe { std::str::from_utf8_unchecked(b) });
+    } else {
+        let mut data = a[head.len()..a_symbols].to_vec();
+        let tail = decode_unpadded(&mut data).len();
+        data.truncate(tail);
+
+        let start = data.len();
+        data.extend_from_slice(&b[..b_symbols]);
+        let len = decode_unpadded(&mut data[start..]).len();
+        data.truncate(start + len);
+
+        out.push_str(&encode(&data));
+    }
+
+    Ok(out)
+}
+
+/// Splits padded standard Base64 into the encodings of the data before and after
+/// `decoded_offset`.
+///
+/// The whole blocks before the offset are copied, only the 1–2 bytes of the block the
+/// offset falls into are re-encoded for the first part. The second part is copied if the
+/// offset is at a block boundary and re-encoded otherwise.
+///
+/// Panics if `decoded_offset` is past the end of the decoded data.
+///
+/// ```
+/// let (first, second) = faster_base64::split_encoded_at("SGVsbG8sIHdvcmxk", 5).unwrap();
+/// assert_eq!(first, faster_base64::encode(b"Hello"));
+/// assert_eq!(second, faster_base64::encode(b", world"));
+/// ```
+pub fn split_encoded_at(
+    encoded: &str,
+    decoded_offset: usize,
+) -> Result<(String, String), DecodeError> {
+    let encoded = encoded.as_bytes();
+    let symbols = symbols_len(encoded)?;
+    if symbols % 4 == 1 {
+        return Err(DecodeError::InvalidLength(symbols));
+    }
+    let decoded_len = decoded_len(symbols);
+    assert!(
+        decoded_offset <= decoded_len,
+        "offset {} out of bounds of {} decoded bytes",
+        decoded_offset,
+        decoded_len
+    );
+
+    let split = decoded_offset / 3 * 4;
+    let head = unsafe { std::str::from_utf8_unchecked(&encoded[..split]) };
+
+    if decoded_offset.is_multiple_of(3) {
+        let rest = unsafe { std::str::from_utf8_unchecked(&encoded[split..]) };
+        return Ok((head.to_string(), rest.to_string()));
+    }
+
+    let mut data = encoded[split..symbols].to_vec();
+    let len = decode_unpadded(&mut data).len();
+    let (tail, rest) = data[..len].split_at(decoded_offset % 3);
+
+    let mut first = String::with_capacity(split + 4);
+    first.push_str(head);
+    first.push_str(&encode(tail));
+
+    Ok((first, encode(rest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{concat_encoded, encode, split_encoded_at, DecodeError};
+
+    #[test]
+    fn concat() {
+        let data = crate::tests::data(9);
+        // Every tail length of both halves, so `a` ends with 0 to 2 bytes to re-encode
+        for a_len in 0..6 {
+            for b_len in 0..4 {
+                let (a, b) = data[..a_len + b_len].split_at(a_len);
+                assert_eq!(
+                    concat_encoded(&encode(a), &encode(b)),
+                    Ok(encode(&data[..a_len + b_len]))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn concat_invalid() {
+        assert_eq!(
+            concat_encoded("SGk=", "SG*="),
+            Err(DecodeError::InvalidByte(6, b'*'))
+        );
+        assert_eq!(
+            concat_encoded("SGk", "SGk="),
+            Err(DecodeError::InvalidLength(3))
+        );
+    }
+
+    #[test]
+    fn split() {
+        let data = crate::tests::data(7);
+        let encoded = encode(&data);
+        // Every offset within a block, and the end after a 1-byte tail
+        for offset in 0..=7 {
+            let (a, b) = data.split_at(offset);
+            assert_eq!(
+                split_encoded_at(&encoded, offset),
+                Ok((encode(a), encode(b)))
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_out_of_bounds() {
+        let _ = split_encoded_at("SGk=", 3);
+    }
+}
@@ -0,0 +1,253 @@
+//! Conversions between padded standard Base64, hex and Base32 without decoding the whole
+//! input to binary first.
+//!
+//! Input is decoded one chunk at a time into a stack buffer and re-encoded from there.
+//!
+//! ```
+//! use faster_base64::convert;
+//!
+//! assert_eq!(convert::base64_to_hex(b"3q2+7w==").unwrap(), "deadbeef");
+//! assert_eq!(convert::hex_to_base64(b"DEADBEEF").unwrap(), "3q2+7w==");
+//! ```
+
+use crate::alphabet::{self, Alphabet};
+use crate::{decode_unpadded, encode_to_slice, padded_symbols_len, DecodeError};
+
+/// Base32 alphabet from RFC 4648.
+pub const BASE32: Alphabet = Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Bytes converted per chunk, whole blocks of Base64 (3 bytes) and Base32 (5 bytes).
+const CHUNK: usize = 15 * 64;
+
+/// Decodes padded standard Base64 a chunk at a time and passes the decoded chunks to `f`.
+///
+/// All chunks but the last are `CHUNK` bytes long.
+fn for_each_decoded(data: &[u8], mut f: impl FnMut(&[u8])) -> Result<(), DecodeError> {
+    let symbols_len = padded_symbols_len(data)?;
+    let mut buf = [0u8; CHUNK / 3 * 4];
+
+    for (i, chunk) in data[..symbols_len].chunks(buf.len()).enumerate() {
+        if let Some(j) = alphabet::STANDARD.find_invalid(chunk) {
+            return Err(DecodeError::InvalidByte(i * buf.len() + j, chunk[j]));
+        }
+        if chunk.len() % 4 == 1 {
+            return Err(DecodeError::InvalidLength(symbols_len));
+        }
+        buf[..chunk.len()].copy_from_slice(chunk);
+        f(decode_unpadded(&mut buf[..chunk.len()]));
+    }
+
+    Ok(())
+}
+
+/// Encodes chunks of decoded data as padded standard Base64.
+struct Base64Output {
+    out: String,
+    buf: [u8; CHUNK / 3 * 4],
+}
+
+impl Base64Output {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            out: String::with_capacity(capacity),
+            buf: [0; CHUNK / 3 * 4],
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        let len = encode_to_slice(chunk, &mut self.buf);
+        self.out
+            .push_str(unsafe { std::str::from_utf8_unchecked(&self.buf[..len]) });
+    }
+}
+
+/// Converts padded standard Base64 to lowercase hex.
+pub fn base64_to_hex(data: &[u8]) -> Result<String, DecodeError> {
+    let mut out = Vec::with_capacity(data.len() / 4 * 6);
+    for_each_decoded(data, |chunk| {
+        for &byte in chunk {
+            out.push(HEX[(byte >> 4) as usize]);
+            out.push(HEX[(byte & 0xf) as usize]);
+        }
+    })?;
+    Ok(unsafe { String::from_utf8_unchecked(out) })
+}
+
+/// Converts hex in either case to padded standard Base64.
+pub fn hex_to_base64(data: &[u8]) -> Result<String, DecodeError> {
+    if !data.len().is_multiple_of(2) {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    let mut output = Base64Output::with_capacity(crate::encoded_len(data.len() / 2));
+    let mut buf = [0u8; CHUNK];
+
+    for (i, chunk) in data.chunks(CHUNK * 2).enumerate() {
+        for (j, pair) in chunk.chunks(2).enumerate() {
+            let digit = |k: usize| {
+                (pair[k] as char)
+                    .to_digit(16)
+                    .ok_or(DecodeError::InvalidByte(i * CHUNK * 2 + j * 2 + k, pair[k]))
+            };
+            buf[j] = (digit(0)? << 4 | digit(1)?) as u8;
+        }
+        output.push(&buf[..chunk.len() / 2]);
+    }
+
+    Ok(output.out)
+}
+
+/// Converts padded standard Base64 to padded Base32.
+pub fn base64_to_base32(data: &[u8]) -> Result<String, DecodeError> {
+    let mut out = Vec::with_capacity(data.len() / 4 * 3 / 5 * 8 + 8);
+    for_each_decoded(data, |chunk| {
+        for block in chunk.chunks(5) {
+            let value = block
+                .iter()
+                .fold(0u64, |value, &byte| value << 8 | byte as u64)
+                << (8 * (5 - block.len()));
+            let symbols = (block.len() * 8).div_ceil(5);
+            for i in 0..8 {
+                out.push(if i < symbols {
+                    BASE32.symbol((value >> (35 - i * 5) & 0x1f) as u8)
+                } else {
+                    b'='
+                });
+            }
+        }
+    })?;
+    Ok(unsafe { String::from_utf8_unchecked(out) })
+}
+
+/// Converts padded Base32 to padded standard Base64.
+pub fn base32_to_base64(data: &[u8]) -> Result<String, DecodeError> {
+    if !data.len().is_multiple_of(8) {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+    let padding = data
+        .iter()
+        .rev()
+        .take(6)
+        .take_while(|&&b| b == b'=')
+        .count();
+    let symbols = &data[..data.len() - padding];
+    if matches!(symbols.len() % 8, 1 | 3 | 6) {
+        return Err(DecodeError::InvalidLength(symbols.len()));
+    }
+
+    let mut output = Base64Output::with_capacity(crate::encoded_len(symbols.len() * 5 / 8));
+    let mut buf = [0u8; CHUNK];
+
+    for (i, chunk) in symbols.chunks(CHUNK / 5 * 8).enumerate() {
+        let mut len = 0;
+        for (j, block) in chunk.chunks(8).enumerate() {
+            let mut value = 0u64;
+            for (k, &symbol) in block.iter().enumerate() {
+                let offset = i * (CHUNK / 5 * 8) + j * 8 + k;
+                let digit = BASE32
+                    .value(symbol)
+                    .ok_or(DecodeError::InvalidByte(offset, symbol))?;
+                value = value << 5 | digit as u64;
+            }
+            value <<= 5 * (8 - block.len());
+
+            let bytes = block.len() * 5 / 8;
+            buf[len..len + bytes].copy_from_slice(&value.to_be_bytes()[3..3 + bytes]);
+            len += bytes;
+        }
+        output.push(&buf[..len]);
+    }
+
+    Ok(output.out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::{base32_to_base64, base64_to_base32, base64_to_hex, hex_to_base64};
+    use crate::DecodeError;
+
+    /// Test vectors from RFC 4648.
+    const BASE32_VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "MY======"),
+        ("fo", "MZXQ===="),
+        ("foo", "MZXW6==="),
+        ("foob", "MZXW6YQ="),
+        ("fooba", "MZXW6YTB"),
+        ("foobar", "MZXW6YTBOI======"),
+    ];
+
+    #[test]
+    fn hex() {
+        let data = crate::tests::data(1921);
+        // Tails of 0, 1 and 2 bytes around chunks of CHUNK bytes
+        for len in [0, 1, 2, 959, 960, 961, 1921] {
+            let hex = data[..len]
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>();
+            let encoded = crate::encode(&data[..len]);
+            assert_eq!(base64_to_hex(encoded.as_bytes()), Ok(hex.clone()));
+            assert_eq!(hex_to_base64(hex.as_bytes()), Ok(encoded.clone()));
+            assert_eq!(hex_to_base64(hex.to_uppercase().as_bytes()), Ok(encoded));
+        }
+    }
+
+    #[test]
+    fn hex_invalid() {
+        assert_eq!(hex_to_base64(b"abc"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(
+            hex_to_base64(b"abcg"),
+            Err(DecodeError::InvalidByte(3, b'g'))
+        );
+        assert_eq!(
+            base64_to_hex(b"SGV*"),
+            Err(DecodeError::InvalidByte(3, b'*'))
+        );
+
+        let mut encoded = crate::encode(&[0; 1920]).into_bytes();
+        encoded[1300] = b'*';
+        assert_eq!(
+            base64_to_hex(&encoded),
+            Err(DecodeError::InvalidByte(1300, b'*'))
+        );
+    }
+
+    #[test]
+    fn base32() {
+        for (data, base32) in BASE32_VECTORS {
+            let encoded = crate::encode(data.as_bytes());
+            assert_eq!(base64_to_base32(encoded.as_bytes()), Ok(base32.to_string()));
+            assert_eq!(base32_to_base64(base32.as_bytes()), Ok(encoded));
+        }
+    }
+
+    #[test]
+    fn base32_long() {
+        let data = crate::tests::data(1921);
+        for len in [959, 960, 961, 1921] {
+            let encoded = crate::encode(&data[..len]);
+            let base32 = base64_to_base32(encoded.as_bytes()).unwrap();
+            assert_eq!(base32.len(), len.div_ceil(5) * 8);
+            assert_eq!(base32_to_base64(base32.as_bytes()), Ok(encoded));
+        }
+    }
+
+    #[test]
+    fn base32_invalid() {
+        assert_eq!(
+            base32_to_base64(b"MZXW6YQ"),
+            Err(DecodeError::InvalidLength(7))
+        );
+        assert_eq!(
+            base32_to_base64(b"MZXW1YQ="),
+            Err(DecodeError::InvalidByte(4, b'1'))
+        );
+        assert_eq!(
+            base32_to_base64(b"MZX====="),
+            Err(DecodeError::InvalidLength(3))
+        );
+    }
+}
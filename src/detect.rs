@@ -0,0 +1,210 @@
+use crate::{alphabet, decode_unpadded, padded_symbols_len, DecodeError};
+
+const INVALID: u8 = 0;
+const COMMON: u8 = 1;
+const STANDARD: u8 = 2;
+const URL_SAFE: u8 = 4;
+
+/// Classes of bytes in the standard and URL-safe alphabets.
+const CLASSES: [u8; 256] = {
+    let mut classes = [INVALID; 256];
+    let mut i = 0;
+    while i < 256 {
+        if (i as u8).is_ascii_alphanumeric() {
+            classes[i] = COMMON;
+        }
+        i += 1;
+    }
+    classes[b'+' as usize] = STANDARD;
+    classes[b'/' as usize] = STANDARD;
+    classes[b'-' as usize] = URL_SAFE;
+    classes[b'_' as usize] = URL_SAFE;
+    classes
+};
+
+/// Alphabet seen by [`decode_any`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedAlphabet {
+    /// Only symbols both alphabets share, so either one decodes the same.
+    Either,
+    Standard,
+    UrlSafe,
+    /// Symbols of both alphabets, only accepted by [`decode_any_mixed`].
+    Mixed,
+}
+
+/// Variant of Base64 seen by [`decode_any`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedVariant {
+    pub alphabet: DetectedAlphabet,
+    /// Whether the input ended with padding. Input that needs no padding reports `false`.
+    pub padded: bool,
+}
+
+/// Decodes standard or URL-safe Base64, with or without padding, in-place.
+///
+/// Symbols of both alphabets in the same input are rejected, see [`decode_any_mixed`].
+/// Returns a slice of `data` with decoded data and the variant that was seen.
+///
+/// ```
+/// use faster_base64::{DetectedAlphabet, DetectedVariant};
+///
+/// let mut data = b"-_8".to_vec();
+/// let (decoded, variant) = faster_base64::decode_any(&mut data).unwrap();
+/// assert_eq!(decoded, b"\xfb\xff");
+/// assert_eq!(
+///     variant,
+///     DetectedVariant { alphabet: DetectedAlphabet::UrlSafe, padded: false }
+/// );
+/// ```
+pub fn decode_any(data: &mut [u8]) -> Result<(&[u8], DetectedVariant), DecodeError> {
+    decode(data, false)
+}
+
+/// Same as [`decode_any`], but also accepts symbols of both alphabets in the same input.
+pub fn decode_any_mixed(data: &mut [u8]) -> Result<(&[u8], DetectedVariant), DecodeError> {
+    decode(data, true)
+}
+
+fn decode(data: &mut [u8], allow_mixed: bool) -> Result<(&[u8], DetectedVariant), DecodeError> {
+    let padded = data.last() == Some(&b'=');
+    let len = if padded {
+        padded_symbols_len(data)?
+    } else {
+        data.len()
+    };
+    let symbols = &mut data[..len];
+
+    if let Some(i) = alphabet::find_chunked(symbols, |b| CLASSES[b as usize] == INVALID) {
+        return Err(DecodeError::InvalidByte(i, symbols[i]));
+    }
+    let seen = symbols
+        .iter()
+        .fold(0, |seen, &b| seen | CLASSES[b as usize]);
+
+    let alphabet = match seen & (STANDARD | URL_SAFE) {
+        0 => DetectedAlphabet::Either,
+        STANDARD => DetectedAlphabet::Standard,
+        URL_SAFE => DetectedAlphabet::UrlSafe,
+        _ if allow_mixed => DetectedAlphabet::Mixed,
+        _ => {
+            let first = |class| {
+                symbols
+                    .iter()
+                    .position(|&b| CLASSES[b as usize] == class)
+                    .unwrap()
+            };
+            let i = first(STANDARD).max(first(URL_SAFE));
+            return Err(DecodeError::InvalidByte(i, symbols[i]));
+        }
+    };
+
+    if len % 4 == 1 {
+        return Err(DecodeError::InvalidLength(len));
+    }
+
+    // Only the symbols that differ are rewritten, the bulk is decoded as standard Base64
+    if seen & URL_SAFE != 0 {
+        for symbol in symbols.iter_mut() {
+            match *symbol {
+                b'-' => *symbol = b'+',
+                b'_' => *symbol = b'/',
+                _ => {}
+            }
+        }
+    }
+
+    Ok((
+        decode_unpadded(symbols),
+        DetectedVariant { alphabet, padded },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine;
+    use crate::{decode_any, decode_any_mixed, DecodeError, DetectedAlphabet, DetectedVariant};
+
+    #[test]
+    fn detect() {
+        // Encodes to "+/+/" or "-_-_" first, so the alphabet is always seen
+        let data = [&b"\xfb\xff\xbf"[..], &crate::tests::data(47)].concat();
+        let cases = [
+            (&engine::STANDARD, DetectedAlphabet::Standard, true),
+            (&engine::STANDARD_NO_PAD, DetectedAlphabet::Standard, false),
+            (&engine::URL_SAFE, DetectedAlphabet::UrlSafe, true),
+            (&engine::URL_SAFE_NO_PAD, DetectedAlphabet::UrlSafe, false),
+        ];
+        // Tails of 0, 1 and 2 bytes, within the first chunk of 64 symbols and past it
+        for len in [3, 4, 5, 48, 49, 50] {
+            for (engine, alphabet, padding) in cases {
+                let mut encoded = engine.encode(&data[..len]).into_bytes();
+                let (decoded, variant) = decode_any(&mut encoded).unwrap();
+                assert_eq!(decoded, &data[..len]);
+                let padded = padding && len % 3 != 0;
+                assert_eq!(variant, DetectedVariant { alphabet, padded });
+            }
+        }
+    }
+
+    #[test]
+    fn detect_across_chunks() {
+        let mut data = [b'A'; 128];
+        data[100] = b'_';
+        let (_, variant) = decode_any(&mut data.clone()).unwrap();
+        assert_eq!(variant.alphabet, DetectedAlphabet::UrlSafe);
+
+        data[64] = b'+';
+        assert_eq!(
+            decode_any(&mut data.clone()).map(|_| ()),
+            Err(DecodeError::InvalidByte(100, b'_'))
+        );
+        data[127] = b'*';
+        assert_eq!(
+            decode_any(&mut data.clone()).map(|_| ()),
+            Err(DecodeError::InvalidByte(127, b'*'))
+        );
+    }
+
+    #[test]
+    fn detect_either() {
+        let mut data = b"SGVsbG8".to_vec();
+        let (decoded, variant) = decode_any(&mut data).unwrap();
+        assert_eq!(decoded, b"Hello");
+        assert_eq!(
+            variant,
+            DetectedVariant {
+                alphabet: DetectedAlphabet::Either,
+                padded: false
+            }
+        );
+    }
+
+    #[test]
+    fn mixed() {
+        assert_eq!(
+            decode_any(&mut b"+/8-_w".to_vec()).map(|_| ()),
+            Err(DecodeError::InvalidByte(3, b'-'))
+        );
+        let mut data = b"+/8-_w==".to_vec();
+        let (decoded, variant) = decode_any_mixed(&mut data).unwrap();
+        assert_eq!(decoded, b"\xfb\xff\x3e\xff");
+        assert_eq!(variant.alphabet, DetectedAlphabet::Mixed);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            decode_any(&mut b"SGVs*G8".to_vec()).map(|_| ()),
+            Err(DecodeError::InvalidByte(4, b'*'))
+        );
+        assert_eq!(
+            decode_any(&mut b"SGVsbG8==".to_vec()).map(|_| ()),
+            Err(DecodeError::InvalidLength(9))
+        );
+        assert_eq!(
+            decode_any(&mut b"SGVsb".to_vec()).map(|_| ()),
+            Err(DecodeError::InvalidLength(5))
+        );
+    }
+}
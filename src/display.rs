@@ -0,0 +1,128 @@
+use std::fmt;
+
+use crate::engine::{self, Engine};
+
+/// Formats bytes as Base64 without allocating.
+///
+/// Width, fill, alignment and precision are honored like for `str`.
+///
+/// ```
+/// use faster_base64::Base64Display;
+///
+/// assert_eq!(format!("{:>10}", Base64Display::new(b"Hello")), "  SGVsbG8=");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Base64Display<'a> {
+    bytes: &'a [u8],
+    engine: &'static Engine,
+}
+
+impl<'a> Base64Display<'a> {
+    /// Formats `bytes` as standard Base64 with padding.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_engine(bytes, &engine::STANDARD)
+    }
+
+    pub fn with_engine(bytes: &'a [u8], engine: &'static Engine) -> Self {
+        Self { bytes, engine }
+    }
+
+    /// Writes at most `limit` bytes of the encoding.
+    fn write_encoded(&self, f: &mut fmt::Formatter<'_>, mut limit: usize) -> fmt::Result {
+        // Whole blocks encode the same on their own, so only those that are written are
+        // encoded
+        let len = self.bytes.len().min(limit.div_ceil(4) * 3);
+        self.engine.encode_chunks(&self.bytes[..len], |encoded| {
+            let len = encoded.len().min(limit);
+            limit -= len;
+            f.write_str(&encoded[..len])
+        })
+    }
+
+    fn write_fill(f: &mut fmt::Formatter<'_>, count: usize) -> fmt::Result {
+        let fill = f.fill();
+        for _ in 0..count {
+            fmt::Write::write_char(f, fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Base64Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut len = self.engine.encoded_len(self.bytes.len());
+        if let Some(precision) = f.precision() {
+            len = len.min(precision);
+        }
+
+        let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+        let (before, after) = match f.align() {
+            None | Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+
+        Self::write_fill(f, before)?;
+        self.write_encoded(f, len)?;
+        Self::write_fill(f, after)
+    }
+}
+
+impl fmt::Debug for Base64Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        self.write_encoded(f, usize::MAX)?;
+        f.write_str("\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine;
+    use crate::Base64Display;
+
+    #[test]
+    fn display() {
+        assert_eq!(Base64Display::new(b"Hello").to_string(), "SGVsbG8=");
+    }
+
+    #[test]
+    fn display_engine() {
+        let display = Base64Display::with_engine(b"\xfb\xff", &engine::URL_SAFE_NO_PAD);
+        assert_eq!(display.to_string(), "-_8");
+    }
+
+    #[test]
+    fn display_long() {
+        let data = crate::tests::data(1537);
+        // Tails of 0, 1 and 2 bytes around the chunks encoded on the stack
+        for len in [767, 768, 769, 1537] {
+            let display = Base64Display::new(&data[..len]);
+            assert_eq!(display.to_string(), crate::encode(&data[..len]));
+        }
+    }
+
+    #[test]
+    fn display_flags() {
+        let display = Base64Display::new(b"Hello");
+        assert_eq!(format!("{:12}|", display), "SGVsbG8=    |");
+        assert_eq!(format!("{:*^12}", display), "**SGVsbG8=**");
+        assert_eq!(format!("{:>6.4}", display), "  SGVs");
+        assert_eq!(format!("{:.0}", display), "");
+    }
+
+    #[test]
+    fn display_precision_across_chunks() {
+        let data = crate::tests::data(1537);
+        let encoded = crate::encode(&data);
+        for precision in [1023, 1024, 1025, 2050, 3000] {
+            let display = format!("{:.*}", precision, Base64Display::new(&data));
+            assert_eq!(display, &encoded[..precision.min(encoded.len())]);
+        }
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Base64Display::new(b"Hi")), "\"SGk=\"");
+    }
+}
@@ -0,0 +1,286 @@
+//! Base64 variants with a different alphabet or without padding.
+//!
+//! Engines run the same kernels as [`encode`](crate::encode) and [`decode`](crate::decode),
+//! translating between their alphabet and the standard one on the fly.
+
+use crate::alphabet::{self, Alphabet};
+use crate::tables::TABLE;
+use crate::{decode_unpadded, encode_to_slice, padded_symbols_len, DecodeError, DecodeLimits};
+
+/// Input bytes encoded per chunk by [`Engine::encode_chunks`], the output of one chunk
+/// stays on the stack.
+const ENCODE_CHUNK: usize = 768;
+
+/// Extent of the Base64 at the start of some input, see [`Engine::run_len`].
+#[cfg(any(feature = "nom", feature = "winnow"))]
+pub(crate) enum Run {
+    /// Length of the symbols and padding.
+    Complete(usize),
+    /// The input ended inside the run, with at least the given number of bytes missing if
+    /// known.
+    Incomplete(Option<usize>),
+}
+
+/// Alphabet and padding configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Engine {
+    alphabet: &'static Alphabet,
+    padding: bool,
+    encode_map: [u8; 256],
+}
+
+/// Standard Base64 with padding, same as [`encode`](crate::encode).
+pub const STANDARD: Engine = Engine::new(&alphabet::STANDARD, true);
+
+/// Standard Base64 without padding.
+pub const STANDARD_NO_PAD: Engine = Engine::new(&alphabet::STANDARD, false);
+
+/// URL and filename safe Base64 with padding.
+pub const URL_SAFE: Engine = Engine::new(&alphabet::URL_SAFE, true);
+
+/// URL and filename safe Base64 without padding.
+pub const URL_SAFE_NO_PAD: Engine = Engine::new(&alphabet::URL_SAFE, false);
+
+impl Engine {
+    /// Creates an engine from a 64 symbol alphabet.
+    pub const fn new(alphabet: &'static Alphabet, padding: bool) -> Self {
+        assert!(
+            alphabet.len() == 64,
+            "Base64 alphabets must have 64 symbols"
+        );
+
+        let mut encode_map = [0u8; 256];
+        let mut i = 0;
+        while i < 64 {
+            encode_map[TABLE[i] as usize] = alphabet.symbol(i as u8);
+            i += 1;
+        }
+        encode_map[b'=' as usize] = b'=';
+
+        Self {
+            alphabet,
+            padding,
+            encode_map,
+        }
+    }
+
+    pub const fn alphabet(&self) -> &'static Alphabet {
+        self.alphabet
+    }
+
+    pub const fn padding(&self) -> bool {
+        self.padding
+    }
+
+    /// Returns the length of encoded output for `len` bytes of input.
+    pub const fn encoded_len(&self, len: usize) -> usize {
+        if self.padding {
+            crate::encoded_len(len)
+        } else {
+            len / 3 * 4 + (len % 3 * 4).div_ceil(3)
+        }
+    }
+
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut out = vec![0u8; crate::encoded_len(data.len())];
+        let len = self.encode_to_slice(data, &mut out);
+        out.truncate(len);
+        unsafe { String::from_utf8_unchecked(out) }
+    }
+
+    /// Encodes `data` into `out`, returning the number of bytes written.
+    ///
+    /// Panics if `out` is shorter than padded output, even when the engine does not pad.
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> usize {
+        let len = encode_to_slice(data, out);
+        self.translate(&mut out[..len]);
+        self.encoded_len(data.len())
+    }
+
+    /// Encodes `data` a chunk at a time without allocating, passing each encoded chunk to
+    /// `write` until it fails.
+    pub(crate) fn encode_chunks<E>(
+        &self,
+        data: &[u8],
+        mut write: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut buf = [0u8; ENCODE_CHUNK / 3 * 4];
+        for chunk in data.chunks(ENCODE_CHUNK) {
+            let len = self.encode_to_slice(chunk, &mut buf);
+            write(unsafe { std::str::from_utf8_unchecked(&buf[..len]) })?;
+        }
+        Ok(())
+    }
+
+    /// Maps standard Base64 symbols in `data` to symbols of this engine's alphabet.
+    fn translate(&self, data: &mut [u8]) {
+        if *self.alphabet != alphabet::STANDARD {
+            for symbol in data.iter_mut() {
+                *symbol = self.encode_map[*symbol as usize];
+            }
+        }
+    }
+
+    /// Decodes `data` in-place, returning a slice of `data` with decoded data.
+    ///
+    /// Padding is required if the engine pads, and rejected otherwise. `data` is left
+    /// unchanged on error.
+    pub fn decode<'a>(&self, data: &'a mut [u8]) -> Result<&'a [u8], DecodeError> {
+        let len = if self.padding {
+            padded_symbols_len(data)?
+        } else {
+            data.len()
+        };
+        let symbols = &mut data[..len];
+
+        if let Some(i) = self.alphabet.find_invalid(symbols) {
+            return Err(DecodeError::InvalidByte(i, symbols[i]));
+        }
+
+        if len % 4 == 1 {
+            return Err(DecodeError::InvalidLength(len));
+        }
+
+        if *self.alphabet != alphabet::STANDARD {
+            for symbol in symbols.iter_mut() {
+                *symbol = TABLE[self.alphabet.value(*symbol).unwrap_or(0) as usize];
+            }
+        }
+
+        Ok(decode_unpadded(symbols))
+    }
+
+    /// Finds the longest run of symbols at the start of `data` and the padding that has to
+    /// follow it.
+    ///
+    /// If `partial` is set, more input can follow `data`, so a run that reaches the end of
+    /// `data` is incomplete.
+    #[cfg(any(feature = "nom", feature = "winnow"))]
+    pub(crate) fn run_len(&self, data: &[u8], partial: bool) -> Result<Run, DecodeError> {
+        let symbols = self.alphabet.find_invalid(data).unwrap_or(data.len());
+
+        if symbols == data.len() && partial {
+            return Ok(Run::Incomplete(None));
+        }
+        if symbols % 4 == 1 {
+            return Err(DecodeError::InvalidLength(symbols));
+        }
+        if !self.padding || symbols.is_multiple_of(4) {
+            return Ok(Run::Complete(symbols));
+        }
+
+        let needed = 4 - symbols % 4;
+        let padding = data[symbols..]
+            .iter()
+            .take(needed)
+            .take_while(|&&b| b == b'=')
+            .count();
+
+        if padding == needed {
+            Ok(Run::Complete(symbols + padding))
+        } else if symbols + padding == data.len() && partial {
+            Ok(Run::Incomplete(Some(needed - padding)))
+        } else {
+            Err(DecodeError::InvalidLength(symbols + padding))
+        }
+    }
+
+    /// Decodes `data` into a new `Vec`.
+    pub fn decode_to_vec(&self, data: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut out = data.to_vec();
+        let len = self.decode(&mut out)?.len();
+        out.truncate(len);
+        Ok(out)
+    }
+
+    /// Same as [`decode_to_vec`](Engine::decode_to_vec), but fails before allocating if
+    /// `data` exceeds `limits`.
+    pub fn decode_to_vec_with_limits(
+        &self,
+        data: &[u8],
+        limits: &DecodeLimits,
+    ) -> Result<Vec<u8>, DecodeError> {
+        limits.check(data)?;
+        self.decode_to_vec(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine;
+    use crate::{DecodeError, DecodeLimits};
+
+    #[test]
+    fn encode_url_safe() {
+        assert_eq!(engine::URL_SAFE.encode(b"\xfb\xff\xbf"), "-_-_");
+    }
+
+    #[test]
+    fn encode_no_pad() {
+        assert_eq!(engine::STANDARD_NO_PAD.encode(b"Hello"), "SGVsbG8");
+        assert_eq!(engine::URL_SAFE_NO_PAD.encode(b"Hell"), "SGVsbA");
+    }
+
+    #[test]
+    fn decode_url_safe() {
+        assert_eq!(
+            engine::URL_SAFE.decode_to_vec(b"-_-_SGVsbA=="),
+            Ok(b"\xfb\xff\xbfHell".to_vec())
+        );
+    }
+
+    #[test]
+    fn decode_no_pad() {
+        assert_eq!(
+            engine::STANDARD_NO_PAD.decode_to_vec(b"SGVsbG8"),
+            Ok(b"Hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn decode_wrong_alphabet() {
+        assert_eq!(
+            engine::URL_SAFE.decode_to_vec(b"+/+/"),
+            Err(DecodeError::InvalidByte(0, b'+'))
+        );
+    }
+
+    #[test]
+    fn decode_unchanged_on_error() {
+        let mut data = *b"-_-_*A==";
+        assert_eq!(
+            engine::URL_SAFE.decode(&mut data),
+            Err(DecodeError::InvalidByte(4, b'*'))
+        );
+        assert_eq!(&data, b"-_-_*A==");
+    }
+
+    #[test]
+    fn decode_padding_rejected() {
+        assert_eq!(
+            engine::STANDARD_NO_PAD.decode_to_vec(b"SGVsbG8="),
+            Err(DecodeError::InvalidByte(7, b'='))
+        );
+    }
+
+    #[test]
+    fn decode_to_vec_with_limits() {
+        let limits = DecodeLimits::new().max_input_len(7);
+        assert_eq!(
+            engine::STANDARD_NO_PAD.decode_to_vec_with_limits(b"SGVsbG8", &limits),
+            Ok(b"Hello".to_vec())
+        );
+        assert_eq!(
+            engine::STANDARD.decode_to_vec_with_limits(b"SGVsbG8=", &limits),
+            Err(DecodeError::LimitExceeded)
+        );
+    }
+
+    #[test]
+    fn decode_padding_required() {
+        assert_eq!(
+            engine::STANDARD.decode_to_vec(b"SGVsbG8"),
+            Err(DecodeError::InvalidLength(7))
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+/// Errors that can occur while decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A byte at the given offset is not part of the alphabet.
+    InvalidByte(usize, u8),
+    /// A UTF-16 code unit at the given offset is not ASCII, so it can not be a symbol.
+    InvalidCodeUnit(usize, u16),
+    /// The last symbol at the given offset has non-zero bits that are discarded when decoding.
+    InvalidLastSymbol(usize, u8),
+    /// The input length is not valid for the encoding.
+    InvalidLength(usize),
+    /// The decoded value does not fit into the output type.
+    Overflow,
+    /// The decoded data is not valid UTF-8, only the given number of decoded bytes are.
+    InvalidUtf8(usize),
+    /// The `begin`/`end` framing of a line-oriented format is missing or malformed.
+    InvalidFraming,
+    /// The input exceeds a [`DecodeLimits`](crate::DecodeLimits) limit.
+    LimitExceeded,
+}
+
+impl DecodeError {
+    /// Shifts offsets and lengths by `offset`, for errors from decoding a part of the input.
+    pub(crate) fn offset_by(self, offset: usize) -> Self {
+        match self {
+            Self::InvalidByte(i, byte) => Self::InvalidByte(offset + i, byte),
+            Self::InvalidCodeUnit(i, unit) => Self::InvalidCodeUnit(offset + i, unit),
+            Self::InvalidLastSymbol(i, byte) => Self::InvalidLastSymbol(offset + i, byte),
+            Self::InvalidLength(len) => Self::InvalidLength(offset + len),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidByte(offset, byte) => {
+                write!(f, "invalid byte {:#04x} at offset {}", byte, offset)
+            }
+            Self::InvalidCodeUnit(offset, unit) => {
+                write!(f, "invalid code unit {:#06x} at offset {}", unit, offset)
+            }
+            Self::InvalidLastSymbol(offset, byte) => {
+                write!(f, "invalid last symbol {:#04x} at offset {}", byte, offset)
+            }
+            Self::InvalidLength(len) => write!(f, "invalid input length {}", len),
+            Self::Overflow => write!(f, "decoded value overflows the output type"),
+            Self::InvalidUtf8(valid_up_to) => {
+                write!(
+                    f,
+                    "decoded data is not valid UTF-8 after {} bytes",
+                    valid_up_to
+                )
+            }
+            Self::InvalidFraming => write!(f, "missing or malformed begin/end lines"),
+            Self::LimitExceeded => write!(f, "input exceeds the decode limits"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
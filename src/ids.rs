@@ -0,0 +1,196 @@
+//! Fixed-width, lexicographically sortable encodings of integer IDs.
+//!
+//! Every value of a given type encodes to the same number of symbols, left-padded with the
+//! zero symbol, so with a sorted alphabet the encoded strings sort in the same order as the
+//! values.
+
+use crate::alphabet::Alphabet;
+use crate::DecodeError;
+
+/// Base62 alphabet (`0-9A-Za-z`), sorted.
+pub const BASE62: Alphabet =
+    Alphabet::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+/// Base36 alphabet (`0-9a-z`), sorted, decodes uppercase as well.
+pub const BASE36: Alphabet = with_uppercase(Alphabet::new(b"0123456789abcdefghijklmnopqrstuvwxyz"));
+
+/// Crockford's Base32 alphabet, sorted, decodes lowercase and the `I`, `L` and `O` aliases.
+pub const CROCKFORD: Alphabet = with_lowercase(Alphabet::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ"))
+    .alias(b'I', b'1')
+    .alias(b'i', b'1')
+    .alias(b'L', b'1')
+    .alias(b'l', b'1')
+    .alias(b'O', b'0')
+    .alias(b'o', b'0');
+
+const fn with_uppercase(mut alphabet: Alphabet) -> Alphabet {
+    let mut i = 0;
+    while i < alphabet.symbols().len() {
+        let symbol = alphabet.symbols()[i];
+        if symbol.is_ascii_lowercase() {
+            alphabet = alphabet.alias(symbol.to_ascii_uppercase(), symbol);
+        }
+        i += 1;
+    }
+    alphabet
+}
+
+const fn with_lowercase(mut alphabet: Alphabet) -> Alphabet {
+    let mut i = 0;
+    while i < alphabet.symbols().len() {
+        let symbol = alphabet.symbols()[i];
+        if symbol.is_ascii_uppercase() {
+            alphabet = alphabet.alias(symbol.to_ascii_lowercase(), symbol);
+        }
+        i += 1;
+    }
+    alphabet
+}
+
+/// Number of symbols needed to encode any value of `bits` bits in `alphabet`.
+const fn width(alphabet: &Alphabet, bits: u32) -> usize {
+    let radix = alphabet.len() as u128;
+    let max = if bits == 128 {
+        u128::MAX
+    } else {
+        (1 << bits) - 1
+    };
+
+    let mut values: u128 = 1;
+    let mut width = 0;
+    loop {
+        width += 1;
+        match values.checked_mul(radix) {
+            Some(next) if next - 1 < max => values = next,
+            _ => return width,
+        }
+    }
+}
+
+/// Length of an encoded `u64` in `alphabet`.
+pub const fn u64_width(alphabet: &Alphabet) -> usize {
+    width(alphabet, 64)
+}
+
+/// Length of an encoded `u128` in `alphabet`.
+pub const fn u128_width(alphabet: &Alphabet) -> usize {
+    width(alphabet, 128)
+}
+
+fn encode(alphabet: &Alphabet, mut value: u128, width: usize) -> String {
+    let radix = alphabet.len() as u128;
+    let mut out = vec![0u8; width];
+
+    for symbol in out.iter_mut().rev() {
+        *symbol = alphabet.symbol((value % radix) as u8);
+        value /= radix;
+    }
+
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+fn decode(alphabet: &Alphabet, data: &[u8], width: usize) -> Result<u128, DecodeError> {
+    if data.len() != width {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    let radix = alphabet.len() as u128;
+    let mut value: u128 = 0;
+
+    for (i, &symbol) in data.iter().enumerate() {
+        let digit = alphabet
+            .value(symbol)
+            .ok_or(DecodeError::InvalidByte(i, symbol))?;
+        value = value
+            .checked_mul(radix)
+            .and_then(|value| value.checked_add(digit as u128))
+            .ok_or(DecodeError::Overflow)?;
+    }
+
+    Ok(value)
+}
+
+/// Encodes `value` into a fixed-width string of [`u64_width`] symbols.
+pub fn encode_u64(alphabet: &Alphabet, value: u64) -> String {
+    encode(alphabet, value as u128, u64_width(alphabet))
+}
+
+/// Decodes a string produced by [`encode_u64`].
+pub fn decode_u64(alphabet: &Alphabet, data: impl AsRef<[u8]>) -> Result<u64, DecodeError> {
+    let value = decode(alphabet, data.as_ref(), u64_width(alphabet))?;
+    u64::try_from(value).map_err(|_| DecodeError::Overflow)
+}
+
+/// Encodes `value` into a fixed-width string of [`u128_width`] symbols.
+pub fn encode_u128(alphabet: &Alphabet, value: u128) -> String {
+    encode(alphabet, value, u128_width(alphabet))
+}
+
+/// Decodes a string produced by [`encode_u128`].
+pub fn decode_u128(alphabet: &Alphabet, data: impl AsRef<[u8]>) -> Result<u128, DecodeError> {
+    decode(alphabet, data.as_ref(), u128_width(alphabet))
+}
+
+/// Encodes 16 bytes (e.g. a UUID) as a big-endian `u128`, so byte-wise order is preserved.
+pub fn encode_bytes(alphabet: &Alphabet, bytes: &[u8; 16]) -> String {
+    encode_u128(alphabet, u128::from_be_bytes(*bytes))
+}
+
+/// Decodes a string produced by [`encode_bytes`].
+pub fn decode_bytes(alphabet: &Alphabet, data: impl AsRef<[u8]>) -> Result<[u8; 16], DecodeError> {
+    decode_u128(alphabet, data).map(u128::to_be_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids;
+    use crate::DecodeError;
+
+    #[test]
+    fn widths() {
+        assert_eq!(ids::u64_width(&ids::BASE62), 11);
+        assert_eq!(ids::u128_width(&ids::BASE62), 22);
+        assert_eq!(ids::u64_width(&ids::BASE36), 13);
+        assert_eq!(ids::u128_width(&ids::CROCKFORD), 26);
+    }
+
+    #[test]
+    fn encode_u64() {
+        assert_eq!(ids::encode_u64(&ids::BASE62, 61), "0000000000z");
+        assert_eq!(ids::encode_u64(&ids::BASE62, u64::MAX), "LygHa16AHYF");
+    }
+
+    #[test]
+    fn encode_sorted() {
+        let a = ids::encode_u64(&ids::CROCKFORD, 31);
+        let b = ids::encode_u64(&ids::CROCKFORD, 32);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn decode_u64() {
+        assert_eq!(ids::decode_u64(&ids::BASE62, "LygHa16AHYF"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn decode_u64_overflow() {
+        assert_eq!(
+            ids::decode_u64(&ids::BASE62, "zzzzzzzzzzz"),
+            Err(DecodeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn decode_aliases() {
+        assert_eq!(ids::decode_u64(&ids::CROCKFORD, "000000000000l"), Ok(1));
+        assert_eq!(ids::decode_u64(&ids::BASE36, "000000000000Z"), Ok(35));
+        assert_eq!(ids::decode_u64(&ids::BASE36, "3W5E11264SGSF"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let bytes = *b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\xff";
+        let encoded = ids::encode_bytes(&ids::BASE36, &bytes);
+        assert_eq!(ids::decode_bytes(&ids::BASE36, encoded), Ok(bytes));
+    }
+}
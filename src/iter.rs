@@ -0,0 +1,235 @@
+use crate::engine::{self, Engine};
+use crate::DecodeError;
+
+#[cfg(feature = "zeroize")]
+use ::zeroize::Zeroize;
+
+/// Input bytes pulled from the source iterator per block.
+const BLOCK: usize = 3 * 64;
+
+/// Lazily encodes bytes from an iterator, returned by [`encode_iter`].
+pub struct EncodeIter<I> {
+    source: I,
+    engine: &'static Engine,
+    input: [u8; BLOCK],
+    out: [u8; BLOCK / 3 * 4],
+    out_pos: usize,
+    out_len: usize,
+}
+
+/// Encodes bytes from `source` as standard Base64 with padding, one block at a time.
+///
+/// ```
+/// let encoded = faster_base64::encode_iter(b"Hello".iter().copied()).collect::<Vec<_>>();
+/// assert_eq!(encoded, b"SGVsbG8=");
+/// ```
+pub fn encode_iter<I: IntoIterator<Item = u8>>(source: I) -> EncodeIter<I::IntoIter> {
+    EncodeIter::with_engine(source.into_iter(), &engine::STANDARD)
+}
+
+impl<I: Iterator<Item = u8>> EncodeIter<I> {
+    pub fn with_engine(source: I, engine: &'static Engine) -> Self {
+        Self {
+            source,
+            engine,
+            input: [0; BLOCK],
+            out: [0; BLOCK / 3 * 4],
+            out_pos: 0,
+            out_len: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for EncodeIter<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.out_pos == self.out_len {
+            let len = fill(&mut self.source, &mut self.input);
+            if len == 0 {
+                return None;
+            }
+
+            // Only the last block is shorter than BLOCK, so padding only ends up there
+            self.out_len = self
+                .engine
+                .encode_to_slice(&self.input[..len], &mut self.out);
+            self.out_pos = 0;
+        }
+
+        self.out_pos += 1;
+        Some(self.out[self.out_pos - 1])
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<I> Drop for EncodeIter<I> {
+    fn drop(&mut self) {
+        self.input.zeroize();
+        self.out.zeroize();
+    }
+}
+
+/// Lazily decodes bytes from an iterator, returned by [`decode_iter`].
+///
+/// After an error is returned, the iterator ends.
+pub struct DecodeIter<I> {
+    source: I,
+    engine: &'static Engine,
+    input: [u8; BLOCK / 3 * 4],
+    out_pos: usize,
+    out_len: usize,
+    symbols_decoded: usize,
+    padding_seen: bool,
+    done: bool,
+}
+
+/// Decodes standard Base64 with padding from `source`, one block at a time.
+///
+/// ```
+/// let decoded = faster_base64::decode_iter(b"SGVsbG8=".iter().copied())
+///     .collect::<Result<Vec<_>, _>>();
+/// assert_eq!(decoded.unwrap(), b"Hello");
+/// ```
+pub fn decode_iter<I: IntoIterator<Item = u8>>(source: I) -> DecodeIter<I::IntoIter> {
+    DecodeIter::with_engine(source.into_iter(), &engine::STANDARD)
+}
+
+impl<I: Iterator<Item = u8>> DecodeIter<I> {
+    pub fn with_engine(source: I, engine: &'static Engine) -> Self {
+        Self {
+            source,
+            engine,
+            input: [0; BLOCK / 3 * 4],
+            out_pos: 0,
+            out_len: 0,
+            symbols_decoded: 0,
+            padding_seen: false,
+            done: false,
+        }
+    }
+
+    /// Decodes the next block in-place at the start of `input`.
+    fn refill(&mut self) -> Result<(), DecodeError> {
+        let len = fill(&mut self.source, &mut self.input);
+        self.out_pos = 0;
+        self.out_len = 0;
+
+        if len == 0 {
+            self.done = true;
+            return Ok(());
+        }
+
+        if self.padding_seen {
+            return Err(DecodeError::InvalidByte(
+                self.symbols_decoded,
+                self.input[0],
+            ));
+        }
+        self.padding_seen = self.input[len - 1] == b'=';
+
+        let offset = self.symbols_decoded;
+        self.out_len = self
+            .engine
+            .decode(&mut self.input[..len])
+            .map_err(|err| err.offset_by(offset))?
+            .len();
+        self.symbols_decoded += len;
+
+        Ok(())
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for DecodeIter<I> {
+    type Item = Result<u8, DecodeError>;
+
+    fn next(&mut self) -> Option<Result<u8, DecodeError>> {
+        while self.out_pos == self.out_len {
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.refill() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+
+        self.out_pos += 1;
+        Some(Ok(self.input[self.out_pos - 1]))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<I> Drop for DecodeIter<I> {
+    fn drop(&mut self) {
+        self.input.zeroize();
+    }
+}
+
+/// Pulls bytes from `source` until `buf` is full or the source ends.
+fn fill(source: &mut impl Iterator<Item = u8>, buf: &mut [u8]) -> usize {
+    let mut len = 0;
+    for (out, byte) in buf.iter_mut().zip(source) {
+        *out = byte;
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine;
+    use crate::iter::{DecodeIter, EncodeIter};
+    use crate::DecodeError;
+
+    #[test]
+    fn encode_iter() {
+        let data = crate::tests::data(385);
+        // Tails of 0, 1 and 2 bytes around blocks of BLOCK bytes
+        for len in [0, 1, 2, 191, 192, 193, 384, 385] {
+            let encoded = crate::encode_iter(data[..len].iter().copied()).collect::<Vec<_>>();
+            assert_eq!(encoded, crate::encode(&data[..len]).as_bytes());
+        }
+    }
+
+    #[test]
+    fn encode_iter_engine() {
+        let encoded = EncodeIter::with_engine(b"Hell".iter().copied(), &engine::URL_SAFE_NO_PAD)
+            .collect::<Vec<_>>();
+        assert_eq!(encoded, b"SGVsbA");
+    }
+
+    #[test]
+    fn decode_iter() {
+        let data = crate::tests::data(385);
+        for len in [0, 1, 2, 191, 192, 193, 384, 385] {
+            let encoded = crate::encode(&data[..len]).into_bytes();
+            let decoded = crate::decode_iter(encoded).collect::<Result<Vec<_>, _>>();
+            assert_eq!(decoded, Ok(data[..len].to_vec()));
+        }
+    }
+
+    #[test]
+    fn decode_iter_engine() {
+        let decoded = DecodeIter::with_engine(b"SGVsbA".iter().copied(), &engine::STANDARD_NO_PAD)
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(decoded, Ok(b"Hell".to_vec()));
+    }
+
+    #[test]
+    fn decode_iter_error() {
+        let mut encoded = crate::encode(&[0; 600]).into_bytes();
+        encoded[500] = b'*';
+        let result = crate::decode_iter(encoded).collect::<Result<Vec<_>, _>>();
+        assert_eq!(result, Err(DecodeError::InvalidByte(500, b'*')));
+    }
+
+    #[test]
+    fn decode_iter_after_padding() {
+        // The first block ends with padding, so the next one can not start
+        let mut encoded = crate::encode(&[0; 191]).into_bytes();
+        encoded.extend_from_slice(b"YQ==");
+        let result = crate::decode_iter(encoded).collect::<Result<Vec<_>, _>>();
+        assert_eq!(result, Err(DecodeError::InvalidByte(256, b'Y')));
+    }
+}
@@ -0,0 +1,124 @@
+//! Safe block kernels for standard Base64 without padding.
+//!
+//! These check the input length and output size once per call and then run the same
+//! kernels as [`unchecked`](crate::unchecked), on aligned and unaligned buffers alike.
+//!
+//! ```
+//! use faster_base64::kernel;
+//!
+//! let mut out = [0u8; 8];
+//! let len = kernel::encode_blocks(b"Hello!", &mut out).unwrap();
+//! assert_eq!(&out[..len], b"SGVsbG8h");
+//!
+//! let mut decoded = [0u8; 6];
+//! let len = kernel::decode_blocks(&out, &mut decoded).unwrap();
+//! assert_eq!(&decoded[..len], b"Hello!");
+//! ```
+
+use std::fmt;
+
+use crate::encode_to_slice;
+
+/// Errors of the block kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernelError {
+    /// The input length is not a whole number of blocks.
+    InvalidLength(usize),
+    /// The output is shorter than the given number of bytes the blocks need.
+    OutputTooSmall(usize),
+}
+
+impl fmt::Display for KernelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(f, "input length {} is not whole blocks", len),
+            Self::OutputTooSmall(len) => write!(f, "output is shorter than {} bytes", len),
+        }
+    }
+}
+
+impl std::error::Error for KernelError {}
+
+/// Encodes whole blocks of 3 bytes from `data` into `out`.
+///
+/// Returns the number of bytes written, which is `data.len() / 3 * 4`.
+pub fn encode_blocks(data: &[u8], out: &mut [u8]) -> Result<usize, KernelError> {
+    if !data.len().is_multiple_of(3) {
+        return Err(KernelError::InvalidLength(data.len()));
+    }
+    let len = data.len() / 3 * 4;
+    if out.len() < len {
+        return Err(KernelError::OutputTooSmall(len));
+    }
+
+    Ok(encode_to_slice(data, out))
+}
+
+/// Decodes whole blocks of 4 symbols from `symbols` into `out`.
+///
+/// Symbols are not validated and invalid ones decode to unspecified bytes, check untrusted
+/// input with [`Alphabet::find_invalid`](crate::alphabet::Alphabet::find_invalid) first.
+/// Returns the number of bytes written, which is `symbols.len() / 4 * 3`.
+pub fn decode_blocks(symbols: &[u8], out: &mut [u8]) -> Result<usize, KernelError> {
+    if !symbols.len().is_multiple_of(4) {
+        return Err(KernelError::InvalidLength(symbols.len()));
+    }
+    let len = symbols.len() / 4 * 3;
+    if out.len() < len {
+        return Err(KernelError::OutputTooSmall(len));
+    }
+
+    crate::decode_blocks(symbols, out);
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kernel::{decode_blocks, encode_blocks, KernelError};
+
+    #[test]
+    fn round_trip_unaligned() {
+        let data = crate::tests::data(300);
+        let mut encoded = [0u8; 403];
+        let mut decoded = [0u8; 303];
+        // No blocks, a single block the lcvec kernels skip, and every output alignment
+        for len in [0, 3, 6, 300] {
+            for offset in 0..4 {
+                let data = &data[..len];
+                let symbols_len = encode_blocks(data, &mut encoded[offset..]).unwrap();
+                let symbols = &encoded[offset..offset + symbols_len];
+                assert_eq!(symbols, crate::encode(data).as_bytes());
+                assert_eq!(decode_blocks(symbols, &mut decoded[offset..]), Ok(len));
+                assert_eq!(&decoded[offset..offset + len], data);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = [0u8; 8];
+        assert_eq!(
+            encode_blocks(b"Hello", &mut out),
+            Err(KernelError::InvalidLength(5))
+        );
+        assert_eq!(
+            encode_blocks(b"Hello, world", &mut out),
+            Err(KernelError::OutputTooSmall(16))
+        );
+        assert_eq!(
+            decode_blocks(b"SGVsbG8", &mut out),
+            Err(KernelError::InvalidLength(7))
+        );
+        assert_eq!(
+            decode_blocks(b"SGVsbG8h", &mut out[..5]),
+            Err(KernelError::OutputTooSmall(6))
+        );
+    }
+
+    #[test]
+    fn invalid_symbols() {
+        // Pairs of any two bytes index the pair table, including the last entry
+        let mut out = [0u8; 12];
+        assert_eq!(decode_blocks(&[0xff; 16], &mut out), Ok(12));
+    }
+}
@@ -1,23 +1,98 @@
+pub mod alphabet;
+mod array;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+mod concat;
+pub mod convert;
+mod detect;
+mod display;
+pub mod engine;
+mod error;
+pub mod ids;
+mod iter;
+pub mod kernel;
+mod limits;
+#[cfg(feature = "nom")]
+pub mod nom;
+mod push;
+pub mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod stream;
+mod string;
 mod tables;
+mod traits;
+mod transcode;
+pub mod unchecked;
+mod utf16;
+pub mod uu;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+pub mod xx;
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
+
+pub use crate::array::{decode_exact, encode_array, ArrayString};
+pub use crate::concat::{concat_encoded, split_encoded_at};
+pub use crate::detect::{decode_any, decode_any_mixed, DetectedAlphabet, DetectedVariant};
+pub use crate::display::Base64Display;
+pub use crate::error::DecodeError;
+pub use crate::iter::{decode_iter, encode_iter, DecodeIter, EncodeIter};
+pub use crate::limits::DecodeLimits;
+pub use crate::push::{Decoder, Encoder};
+pub use crate::string::{Base64Str, Base64String};
+pub use crate::traits::{FromBase64, ToBase64};
+pub use crate::transcode::{transcode, transcode_in_place};
+pub use crate::utf16::{decode_utf16, encode_utf16};
+
+use std::ops::Range;
 
 use crate::tables::*;
+use crate::unchecked::{
+    lcvec_dec, lcvec_dec_in_place, lcvec_dec_unaligned, lcvec_enc, lcvec_enc_unaligned,
+};
+
+/// Returns the length of padded Base64 for `len` bytes of input.
+pub const fn encoded_len(len: usize) -> usize {
+    len.div_ceil(3) * 4
+}
+
+/// Returns the length of the data decoded from `symbols` Base64 symbols without padding.
+pub const fn decoded_len(symbols: usize) -> usize {
+    symbols / 4 * 3 + symbols % 4 * 3 / 4
+}
 
 pub fn encode(data: &[u8]) -> String {
-    let remainder = data.len() % 3;
-    let out_size = if remainder == 0 {
-        (data.len() / 3) * 4
-    } else {
-        ((data.len() / 3) * 4) + 4
-    };
+    let mut out = vec![0u8; encoded_len(data.len())];
+    encode_to_slice(data, &mut out);
+    unsafe { String::from_utf8_unchecked(out) }
+}
 
-    let mut out = vec![0u8; out_size];
+/// Encodes padded Base64 into `out`.
+///
+/// Returns the number of bytes written, which is `encoded_len(data.len())`.
+///
+/// Panics if `out` is shorter than that.
+pub fn encode_to_slice(data: &[u8], out: &mut [u8]) -> usize {
+    let out_size = encoded_len(data.len());
+    assert!(out.len() >= out_size, "output buffer is too small");
 
-    if data.len() - 3 > 0 {
+    if data.is_empty() {
+        return 0;
+    }
+
+    let remainder = data.len() % 3;
+
+    if data.len() > 3 {
         unsafe {
-            lcvec_enc(
-                data,
-                std::slice::from_raw_parts_mut(out.as_mut_ptr() as *mut u32, out.len() / 2),
-            );
+            if out.as_ptr().align_offset(4) == 0 {
+                lcvec_enc(
+                    data,
+                    std::slice::from_raw_parts_mut(out.as_mut_ptr() as *mut u32, out_size / 4),
+                );
+            } else {
+                lcvec_enc_unaligned(data, out);
+            }
         }
     }
 
@@ -30,54 +105,28 @@ pub fn encode(data: &[u8]) -> String {
 
     if remainder == 1 {
         let value = (data[last_chunk_pos] as usize) << 16;
-        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f] as u8;
-        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f] as u8;
+        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f];
+        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f];
         out[out_chunk_pos + 2] = b'=';
         out[out_chunk_pos + 3] = b'=';
     } else if remainder == 2 {
         let value =
             (data[last_chunk_pos] as usize) << 16 | (data[last_chunk_pos + 1] as usize) << 8;
-        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f] as u8;
-        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f] as u8;
-        out[out_chunk_pos + 2] = TABLE[value >> 6 & 0x3f] as u8;
+        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f];
+        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f];
+        out[out_chunk_pos + 2] = TABLE[value >> 6 & 0x3f];
         out[out_chunk_pos + 3] = b'=';
     } else {
         let value = (data[last_chunk_pos] as usize) << 16
             | (data[last_chunk_pos + 1] as usize) << 8
             | data[last_chunk_pos + 2] as usize;
-        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f] as u8;
-        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f] as u8;
-        out[out_chunk_pos + 2] = TABLE[value >> 6 & 0x3f] as u8;
-        out[out_chunk_pos + 3] = TABLE[value & 0x3f] as u8;
-    }
-
-    unsafe { String::from_utf8_unchecked(out) }
-}
-
-/// Large Chunk Vectorization decoder
-pub unsafe fn lcvec_dec(s: &[u16], out: &mut [u8]) {
-    let i_max = s.len() - 2;
-    let j_max = (s.len() / 2) * 3;
-    for (i, j) in (0..i_max).step_by(2).zip((0..j_max).step_by(3)) {
-        let value = (*REVERSE_PAIR_TABLE.get_unchecked(*s.get_unchecked(i) as usize) as u32) << 12
-            | *REVERSE_PAIR_TABLE.get_unchecked(*s.get_unchecked(i + 1) as usize) as u32;
-        *out.get_unchecked_mut(j) = (value >> 16) as u8;
-        *out.get_unchecked_mut(j + 1) = (value >> 8) as u8;
-        *out.get_unchecked_mut(j + 2) = value as u8;
+        out[out_chunk_pos] = TABLE[value >> 18 & 0x3f];
+        out[out_chunk_pos + 1] = TABLE[value >> 12 & 0x3f];
+        out[out_chunk_pos + 2] = TABLE[value >> 6 & 0x3f];
+        out[out_chunk_pos + 3] = TABLE[value & 0x3f];
     }
-}
 
-/// Large Chunk Vectorization encoder
-pub unsafe fn lcvec_enc(s: &[u8], out: &mut [u32]) {
-    let i_max = s.len() - 3;
-    let j_max = s.len() / 3;
-    for (i, j) in (0..i_max).step_by(3).zip(0..j_max) {
-        let value = (*s.get_unchecked(i) as usize) << 16
-            | (*s.get_unchecked(i + 1) as usize) << 8
-            | *s.get_unchecked(i + 2) as usize;
-        *out.get_unchecked_mut(j) = *PAIR_TABLE.get_unchecked(value >> 12 & 0xfff) as u32
-            | (*PAIR_TABLE.get_unchecked(value & 0xfff) as u32) << 16;
-    }
+    out_size
 }
 
 /// Decodes Base64, uses `data` as a buffer for in-place decoding.
@@ -90,10 +139,7 @@ pub fn decode(data: &mut [u8]) -> &[u8] {
 
     if data.len() - 4 > 0 {
         unsafe {
-            lcvec_dec(
-                std::slice::from_raw_parts(data.as_ptr() as *const u16, data.len() / 2),
-                data,
-            );
+            lcvec_dec_in_place(data);
         }
     }
 
@@ -118,15 +164,272 @@ pub fn decode(data: &mut [u8]) -> &[u8] {
     &data[..out_chunk_pos + last_chunk_len]
 }
 
+/// Decodes Base64 like [`decode`], but only accepts the canonical encoding of the data.
+///
+/// Every byte must be part of the alphabet, padding is only allowed to complete the last
+/// chunk and the bits discarded from the last symbol must be zero, so each byte string has
+/// exactly one accepted encoding.
+pub fn decode_strict(data: &mut [u8]) -> Result<&[u8], DecodeError> {
+    validate_canonical(data)?;
+    Ok(decode(data))
+}
+
+/// Returns the number of symbols before the padding of padded Base64.
+pub(crate) fn padded_symbols_len(data: &[u8]) -> Result<usize, DecodeError> {
+    if !data.len().is_multiple_of(4) {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    let padding = data
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&b| b == b'=')
+        .count();
+
+    Ok(data.len() - padding)
+}
+
+pub(crate) fn validate_canonical(data: &[u8]) -> Result<(), DecodeError> {
+    let symbols = &data[..padded_symbols_len(data)?];
+    if symbols.is_empty() {
+        return Ok(());
+    }
+    let padding = data.len() - symbols.len();
+
+    if let Some(i) = alphabet::STANDARD.find_invalid(symbols) {
+        return Err(DecodeError::InvalidByte(i, symbols[i]));
+    }
+
+    let last = symbols.len() - 1;
+    let discarded_bits = match padding {
+        0 => 0,
+        1 => 0b11,
+        _ => 0b1111,
+    };
+    if REVERSE_TABLE[symbols[last] as usize] & discarded_bits != 0 {
+        return Err(DecodeError::InvalidLastSymbol(last, symbols[last]));
+    }
+
+    Ok(())
+}
+
+/// Decodes Base64 in-place following the forgiving-base64 decode algorithm from the WHATWG
+/// Infra standard, matching `atob()` in browsers.
+///
+/// ASCII whitespace is ignored, padding is optional and the bits discarded from the last
+/// symbol may be non-zero.
+pub fn decode_forgiving(data: &mut [u8]) -> Result<&[u8], DecodeError> {
+    let mut len = 0;
+    let mut first_padding = None;
+
+    for i in 0..data.len() {
+        let byte = data[i];
+        match byte {
+            b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' => continue,
+            b'=' => {
+                first_padding.get_or_insert(i);
+            }
+            _ if alphabet::STANDARD.value(byte).is_none() => {
+                return Err(DecodeError::InvalidByte(i, byte));
+            }
+            _ => {}
+        }
+        data[len] = byte;
+        len += 1;
+    }
+
+    let padding = if len % 4 == 0 {
+        data[..len]
+            .iter()
+            .rev()
+            .take(2)
+            .take_while(|&&b| b == b'=')
+            .count()
+    } else {
+        0
+    };
+    let data = &mut data[..len - padding];
+
+    if data.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    if let Some(i) = first_padding.filter(|_| data.contains(&b'=')) {
+        return Err(DecodeError::InvalidByte(i, b'='));
+    }
+
+    Ok(decode_unpadded(data))
+}
+
+/// Decodes only the bytes in `range` of the decoded data of padded standard Base64.
+///
+/// Just the blocks that overlap `range` are validated and decoded, everything else in
+/// `data` is only used for its length.
+///
+/// Panics if `range` is out of bounds of the decoded data.
+///
+/// ```
+/// let decoded = faster_base64::decode_range(b"SGVsbG8sIHdvcmxk", 7..12).unwrap();
+/// assert_eq!(decoded, b"world");
+/// ```
+pub fn decode_range(data: &[u8], range: Range<usize>) -> Result<Vec<u8>, DecodeError> {
+    let symbols_len = padded_symbols_len(data)?;
+    if symbols_len % 4 == 1 {
+        return Err(DecodeError::InvalidLength(symbols_len));
+    }
+    let decoded_len = decoded_len(symbols_len);
+    assert!(
+        range.start <= range.end && range.end <= decoded_len,
+        "range {:?} out of bounds of {} decoded bytes",
+        range,
+        decoded_len
+    );
+    if range.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let first = range.start / 3 * 4;
+    let last = symbols_len.min(range.end.div_ceil(3) * 4);
+    let mut out = data[first..last].to_vec();
+    if let Some(i) = alphabet::STANDARD.find_invalid(&out) {
+        return Err(DecodeError::InvalidByte(first + i, out[i]));
+    }
+
+    let len = decode_unpadded(&mut out).len();
+    out.truncate(len.min(range.end - range.start / 3 * 3));
+    out.drain(..range.start % 3);
+    Ok(out)
+}
+
+/// Symbols decoded per chunk by [`decode_to_str`], the output is validated while in cache.
+const UTF8_CHUNK: usize = 16 * 1024;
+
+/// Decodes padded Base64 in-place and validates that the result is UTF-8.
+///
+/// Validation runs on each chunk right after it is decoded instead of as a second pass.
+/// Invalid UTF-8 is reported as [`DecodeError::InvalidUtf8`] with an offset in the decoded
+/// data.
+pub fn decode_to_str(data: &mut [u8]) -> Result<&str, DecodeError> {
+    let symbols_len = padded_symbols_len(data)?;
+    if let Some(i) = alphabet::STANDARD.find_invalid(&data[..symbols_len]) {
+        return Err(DecodeError::InvalidByte(i, data[i]));
+    }
+
+    let mut out_len = 0;
+    let mut unvalidated = 0;
+
+    for start in (0..symbols_len).step_by(UTF8_CHUNK) {
+        let end = symbols_len.min(start + UTF8_CHUNK);
+        let len = decode_unpadded(&mut data[start..end]).len();
+        data.copy_within(start..start + len, out_len);
+        out_len += len;
+
+        if let Err(err) = std::str::from_utf8(&data[unvalidated..out_len]) {
+            // A character split between chunks is validated with the next chunk
+            if err.error_len().is_some() || end == symbols_len {
+                return Err(DecodeError::InvalidUtf8(unvalidated + err.valid_up_to()));
+            }
+            unvalidated += err.valid_up_to();
+        } else {
+            unvalidated = out_len;
+        }
+    }
+
+    Ok(unsafe { std::str::from_utf8_unchecked(&data[..out_len]) })
+}
+
+/// Decodes padded Base64 into a new `String`, see [`decode_to_str`].
+pub fn decode_to_string(data: &[u8]) -> Result<String, DecodeError> {
+    let mut out = data.to_vec();
+    let len = decode_to_str(&mut out)?.len();
+    out.truncate(len);
+    Ok(unsafe { String::from_utf8_unchecked(out) })
+}
+
+/// Decodes whole blocks of standard symbols from `symbols` into `out`.
+///
+/// `symbols` must have a length that is a multiple of 4 and only hold alphabet symbols,
+/// `out` must have room for the decoded blocks.
+pub(crate) fn decode_blocks(symbols: &[u8], out: &mut [u8]) {
+    assert!(symbols.len().is_multiple_of(4) && out.len() >= symbols.len() / 4 * 3);
+    if symbols.is_empty() {
+        return;
+    }
+
+    // The kernels skip the last block, which is decoded below
+    unsafe {
+        if symbols.as_ptr().align_offset(2) == 0 {
+            lcvec_dec(
+                std::slice::from_raw_parts(symbols.as_ptr() as *const u16, symbols.len() / 2),
+                out,
+            );
+        } else {
+            lcvec_dec_unaligned(symbols, out);
+        }
+    }
+
+    let last = &symbols[symbols.len() - 4..];
+    let value = REVERSE_TABLE[last[0] as usize] << 18
+        | REVERSE_TABLE[last[1] as usize] << 12
+        | REVERSE_TABLE[last[2] as usize] << 6
+        | REVERSE_TABLE[last[3] as usize];
+    let out_pos = symbols.len() / 4 * 3 - 3;
+    out[out_pos..out_pos + 3].copy_from_slice(&value.to_be_bytes()[1..]);
+}
+
+/// Decodes Base64 without padding in-place.
+///
+/// `data` must only contain alphabet symbols and its length must not be `4n + 1`.
+pub(crate) fn decode_unpadded(data: &mut [u8]) -> &[u8] {
+    let tail = data.len() % 4;
+    let tail_pos = data.len() - tail;
+    let out_pos = if tail_pos > 0 {
+        decode(&mut data[..tail_pos]).len()
+    } else {
+        0
+    };
+
+    if tail == 0 {
+        return &data[..out_pos];
+    }
+
+    let value = data[tail_pos..]
+        .iter()
+        .fold(0, |value, &b| value << 6 | REVERSE_TABLE[b as usize])
+        << (6 * (4 - tail));
+    let tail_len = tail - 1;
+
+    data[out_pos..out_pos + tail_len].copy_from_slice(&value.to_be_bytes()[1..tail_len + 1]);
+
+    &data[..out_pos + tail_len]
+}
+
 #[cfg(test)]
 mod tests {
     use crate as faster_base64;
+    use crate::DecodeError;
+
+    /// Returns `len` bytes of test data that cover every byte value.
+    pub(crate) fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7) as u8).collect()
+    }
 
     #[test]
     fn encode() {
         assert_eq!(faster_base64::encode(b"Hello"), "SGVsbG8=");
     }
 
+    #[test]
+    fn encode_nothing() {
+        assert_eq!(faster_base64::encode(b""), "");
+    }
+
+    #[test]
+    fn encode_less_than_3() {
+        assert_eq!(faster_base64::encode(b"He"), "SGU=");
+    }
+
     #[test]
     fn encode_chunk_equal() {
         assert_eq!(faster_base64::encode(b"Hel"), "SGVs");
@@ -145,6 +448,16 @@ mod tests {
         assert_eq!(faster_base64::encode(b"Hell"), "SGVsbA==");
     }
 
+    #[test]
+    fn decoded_len() {
+        for len in 0..10 {
+            let symbols = faster_base64::encode(&[0; 10][..len])
+                .trim_end_matches('=')
+                .len();
+            assert_eq!(faster_base64::decoded_len(symbols), len);
+        }
+    }
+
     #[test]
     fn decode() {
         assert_eq!(
@@ -186,4 +499,252 @@ mod tests {
             b"Hell"
         );
     }
+
+    #[test]
+    fn decode_unaligned() {
+        let mut data = [0; 21];
+        // Start the symbols at an odd address
+        let offset = (data.as_ptr() as usize + 1) % 2;
+        data[offset..offset + 20].copy_from_slice(b"SGVsSGVsSGVsSGVsbA==");
+        assert_eq!(
+            faster_base64::decode(&mut data[offset..offset + 20]),
+            b"HelHelHelHell"
+        );
+    }
+
+    #[test]
+    fn decode_strict() {
+        assert_eq!(
+            faster_base64::decode_strict(&mut "SGVsbG8=".as_bytes().to_vec()),
+            Ok(&b"Hello"[..])
+        );
+    }
+
+    #[test]
+    fn decode_strict_trailing_bits() {
+        assert_eq!(
+            faster_base64::decode_strict(&mut "SGVsbG9=".as_bytes().to_vec()),
+            Err(DecodeError::InvalidLastSymbol(6, b'9'))
+        );
+    }
+
+    #[test]
+    fn decode_strict_padding_in_middle() {
+        assert_eq!(
+            faster_base64::decode_strict(&mut "SG=sbG8=".as_bytes().to_vec()),
+            Err(DecodeError::InvalidByte(2, b'='))
+        );
+    }
+
+    #[test]
+    fn decode_strict_overlong_padding() {
+        assert_eq!(
+            faster_base64::decode_strict(&mut "SGVsb===".as_bytes().to_vec()),
+            Err(DecodeError::InvalidByte(5, b'='))
+        );
+    }
+
+    #[test]
+    fn decode_strict_invalid_length() {
+        assert_eq!(
+            faster_base64::decode_strict(&mut "SGVsbG8".as_bytes().to_vec()),
+            Err(DecodeError::InvalidLength(7))
+        );
+    }
+
+    /// Vectors from the web-platform-tests `html/webappapis/atob/base64.any.js`.
+    const FORGIVING_VECTORS: &[(&str, Option<&[u8]>)] = &[
+        ("", Some(&[])),
+        ("abcd", Some(&[105, 183, 29])),
+        (" abcd", Some(&[105, 183, 29])),
+        ("abcd ", Some(&[105, 183, 29])),
+        (" abcd===", None),
+        ("abcd=== ", None),
+        ("abcd ===", None),
+        ("a", None),
+        ("ab", Some(&[105])),
+        ("abc", Some(&[105, 183])),
+        ("abcde", None),
+        ("\u{10000}", None),
+        ("=", None),
+        ("==", None),
+        ("===", None),
+        ("====", None),
+        ("=====", None),
+        ("a=", None),
+        ("a==", None),
+        ("a===", None),
+        ("a====", None),
+        ("a=====", None),
+        ("ab=", None),
+        ("ab==", Some(&[105])),
+        ("ab===", None),
+        ("ab====", None),
+        ("ab=====", None),
+        ("abc=", Some(&[105, 183])),
+        ("abc==", None),
+        ("abc===", None),
+        ("abc====", None),
+        ("abc=====", None),
+        ("abcd=", None),
+        ("abcd==", None),
+        ("abcd===", None),
+        ("abcd====", None),
+        ("abcd=====", None),
+        ("abcde=", None),
+        ("abcde==", None),
+        ("abcde===", None),
+        ("abcde====", None),
+        ("abcde=====", None),
+        ("=a", None),
+        ("=a=", None),
+        ("a=b", None),
+        ("a=b=", None),
+        ("ab=c", None),
+        ("ab=c=", None),
+        ("abc=d", None),
+        ("abc=d=", None),
+        ("ab\u{000B}cd", None),
+        ("ab\u{3000}cd", None),
+        ("ab\u{3001}cd", None),
+        ("ab\tcd", Some(&[105, 183, 29])),
+        ("ab\ncd", Some(&[105, 183, 29])),
+        ("ab\u{000C}cd", Some(&[105, 183, 29])),
+        ("ab\rcd", Some(&[105, 183, 29])),
+        ("ab cd", Some(&[105, 183, 29])),
+        ("ab\u{00a0}cd", None),
+        ("ab\t\n\u{000C}\r cd", Some(&[105, 183, 29])),
+        (
+            " \t\n\u{000C}\r ab\t\n\u{000C}\r cd\t\n\u{000C}\r ",
+            Some(&[105, 183, 29]),
+        ),
+        (
+            "ab\t\n\u{000C}\r =\t\n\u{000C}\r =\t\n\u{000C}\r ",
+            Some(&[105]),
+        ),
+        ("A", None),
+        ("/A", Some(&[252])),
+        ("//A", Some(&[255, 240])),
+        ("///A", Some(&[255, 255, 192])),
+        ("////A", None),
+        ("/", None),
+        ("A/", Some(&[3])),
+        ("AA/", Some(&[0, 15])),
+        ("AAAA/", None),
+        ("AAA/", Some(&[0, 0, 63])),
+        ("\u{0000}nonsense", None),
+        ("abcd\u{0000}nonsense", None),
+        ("YQ", Some(&[97])),
+        ("YR", Some(&[97])),
+        ("~~", None),
+        ("..", None),
+        ("--", None),
+        ("__", None),
+    ];
+
+    #[test]
+    fn decode_forgiving() {
+        for (input, expected) in FORGIVING_VECTORS {
+            let mut data = input.as_bytes().to_vec();
+            assert_eq!(
+                faster_base64::decode_forgiving(&mut data).ok(),
+                *expected,
+                "input {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn decode_forgiving_long() {
+        assert_eq!(
+            faster_base64::decode_forgiving(
+                &mut "SGVsSGVs\r\nSGVsSGVsSGVs SGVsSGVsSGVz".as_bytes().to_vec()
+            ),
+            Ok(&b"HelHelHelHelHelHelHelHes"[..])
+        );
+    }
+
+    #[test]
+    fn decode_forgiving_padding_in_middle() {
+        assert_eq!(
+            faster_base64::decode_forgiving(&mut " ab=c".as_bytes().to_vec()),
+            Err(DecodeError::InvalidByte(3, b'='))
+        );
+    }
+
+    #[test]
+    fn decode_to_str() {
+        assert_eq!(
+            faster_base64::decode_to_str(&mut "0J/RgNC40LLQtdGC".as_bytes().to_vec()),
+            Ok("Привет")
+        );
+    }
+
+    #[test]
+    fn decode_to_string_long() {
+        let text = "Grüße, 世界! ".repeat(5000);
+        assert_eq!(
+            faster_base64::decode_to_string(faster_base64::encode(text.as_bytes()).as_bytes()),
+            Ok(text)
+        );
+    }
+
+    #[test]
+    fn decode_to_str_invalid_utf8() {
+        assert_eq!(
+            faster_base64::decode_to_str(&mut "SGn/".as_bytes().to_vec()),
+            Err(DecodeError::InvalidUtf8(2))
+        );
+    }
+
+    #[test]
+    fn decode_to_str_truncated_utf8() {
+        assert_eq!(
+            faster_base64::decode_to_str(&mut "SGnQ".as_bytes().to_vec()),
+            Err(DecodeError::InvalidUtf8(2))
+        );
+    }
+
+    #[test]
+    fn decode_to_str_invalid_base64() {
+        assert_eq!(
+            faster_base64::decode_to_str(&mut "SG*/".as_bytes().to_vec()),
+            Err(DecodeError::InvalidByte(2, b'*'))
+        );
+    }
+
+    #[test]
+    fn decode_range() {
+        let data = data(1000);
+        let encoded = faster_base64::encode(&data);
+        // Ranges starting and ending at every position within a block, and in the 1-byte tail
+        for range in [
+            0..1000,
+            1..2,
+            2..3,
+            3..7,
+            5..500,
+            998..1000,
+            999..1000,
+            10..10,
+        ] {
+            assert_eq!(
+                faster_base64::decode_range(encoded.as_bytes(), range.clone()),
+                Ok(data[range].to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn decode_range_skips_other_blocks() {
+        assert_eq!(
+            faster_base64::decode_range(b"****SGVsbG8=", 3..5),
+            Ok(b"He".to_vec())
+        );
+        assert_eq!(
+            faster_base64::decode_range(b"****SGVsbG8=", 2..5),
+            Err(DecodeError::InvalidByte(0, b'*'))
+        );
+    }
 }
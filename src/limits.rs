@@ -0,0 +1,100 @@
+use crate::{decoded_len, padded_symbols_len, DecodeError};
+
+/// Limits for decoding untrusted input, checked before decoding or allocating.
+///
+/// Exceeding a limit fails with [`DecodeError::LimitExceeded`]. All limits are off by
+/// default.
+///
+/// ```
+/// use faster_base64::{engine, DecodeError, DecodeLimits};
+///
+/// let limits = DecodeLimits::new().max_decoded_len(4);
+/// assert_eq!(
+///     engine::STANDARD.decode_to_vec_with_limits(b"SGVsbA==", &limits),
+///     Ok(b"Hell".to_vec())
+/// );
+/// assert_eq!(
+///     engine::STANDARD.decode_to_vec_with_limits(b"SGVsbG8=", &limits),
+///     Err(DecodeError::LimitExceeded)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub(crate) max_decoded_len: usize,
+    pub(crate) max_line_len: usize,
+    pub(crate) max_input_len: usize,
+}
+
+impl DecodeLimits {
+    /// Creates limits that allow any input.
+    pub const fn new() -> Self {
+        Self {
+            max_decoded_len: usize::MAX,
+            max_line_len: usize::MAX,
+            max_input_len: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum number of decoded bytes.
+    pub const fn max_decoded_len(mut self, max_decoded_len: usize) -> Self {
+        self.max_decoded_len = max_decoded_len;
+        self
+    }
+
+    /// Sets the maximum number of bytes between line breaks.
+    pub const fn max_line_len(mut self, max_line_len: usize) -> Self {
+        self.max_line_len = max_line_len;
+        self
+    }
+
+    /// Sets the maximum number of input bytes, including padding and line breaks.
+    pub const fn max_input_len(mut self, max_input_len: usize) -> Self {
+        self.max_input_len = max_input_len;
+        self
+    }
+
+    /// Checks input that is decoded as a whole and can not contain line breaks, which only
+    /// looks at its length and the padding at its end.
+    pub(crate) fn check(&self, data: &[u8]) -> Result<(), DecodeError> {
+        let symbols = padded_symbols_len(data).unwrap_or(data.len());
+        let decoded_len = decoded_len(symbols);
+
+        if data.len() > self.max_input_len
+            || data.len() > self.max_line_len
+            || decoded_len > self.max_decoded_len
+        {
+            return Err(DecodeError::LimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DecodeError, DecodeLimits};
+
+    #[test]
+    fn check() {
+        let limits = DecodeLimits::new()
+            .max_decoded_len(5)
+            .max_input_len(8)
+            .max_line_len(12);
+        assert_eq!(limits.check(b"SGVsbG8="), Ok(()));
+        assert_eq!(limits.check(b"SGVsbG8h"), Err(DecodeError::LimitExceeded));
+        assert_eq!(
+            limits.check(b"SGVsbG8hIQ=="),
+            Err(DecodeError::LimitExceeded)
+        );
+        assert_eq!(
+            DecodeLimits::new().max_line_len(7).check(b"SGVsbG8="),
+            Err(DecodeError::LimitExceeded)
+        );
+        assert_eq!(DecodeLimits::default().check(&[b'A'; 4096]), Ok(()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Parser combinators for Base64 fields in `nom` parsers.
+//!
+//! The parsers consume the longest run of symbols at the start of the input, followed by
+//! padding if the engine pads, and return the decoded bytes. Invalid runs fail with an
+//! error built from the [`DecodeError`] through `FromExternalError`.
+//!
+//! ```
+//! use faster_base64::engine;
+//! use faster_base64::nom::complete::base64_bytes;
+//! use nom::bytes::complete::tag;
+//! use nom::sequence::preceded;
+//! use nom::Parser;
+//!
+//! let mut field = preceded(tag("key="), base64_bytes::<nom::error::Error<_>>(&engine::STANDARD));
+//! assert_eq!(field.parse(&b"key=SGk=;"[..]), Ok((&b";"[..], b"Hi".to_vec())));
+//! ```
+
+use ::nom::error::{ErrorKind, FromExternalError, ParseError};
+use ::nom::{Err, IResult, Needed};
+
+use crate::engine::{Engine, Run};
+use crate::DecodeError;
+
+fn parse<'a, E>(engine: &Engine, input: &'a [u8], partial: bool) -> IResult<&'a [u8], Vec<u8>, E>
+where
+    E: ParseError<&'a [u8]> + FromExternalError<&'a [u8], DecodeError>,
+{
+    let error = |err| Err::Error(E::from_external_error(input, ErrorKind::Verify, err));
+
+    let len = match engine.run_len(input, partial).map_err(error)? {
+        Run::Complete(len) => len,
+        Run::Incomplete(None) => return Err(Err::Incomplete(Needed::Unknown)),
+        Run::Incomplete(Some(needed)) => return Err(Err::Incomplete(Needed::new(needed))),
+    };
+    let decoded = engine.decode_to_vec(&input[..len]).map_err(error)?;
+
+    Ok((&input[len..], decoded))
+}
+
+/// Parsers for complete input, where the end of the input ends the run.
+pub mod complete {
+    use ::nom::error::{FromExternalError, ParseError};
+    use ::nom::IResult;
+
+    use crate::engine::Engine;
+    use crate::DecodeError;
+
+    /// Parses a Base64 run of `engine` and returns the decoded bytes.
+    pub fn base64_bytes<'a, E>(
+        engine: &'static Engine,
+    ) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<u8>, E>
+    where
+        E: ParseError<&'a [u8]> + FromExternalError<&'a [u8], DecodeError>,
+    {
+        move |input| super::parse(engine, input, false)
+    }
+}
+
+/// Parsers for streaming input, a run that reaches the end of the input is
+/// `Err::Incomplete`.
+pub mod streaming {
+    use ::nom::error::{FromExternalError, ParseError};
+    use ::nom::IResult;
+
+    use crate::engine::Engine;
+    use crate::DecodeError;
+
+    /// Parses a Base64 run of `engine` and returns the decoded bytes.
+    pub fn base64_bytes<'a, E>(
+        engine: &'static Engine,
+    ) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<u8>, E>
+    where
+        E: ParseError<&'a [u8]> + FromExternalError<&'a [u8], DecodeError>,
+    {
+        move |input| super::parse(engine, input, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::nom::error::Error;
+    use ::nom::{Err, IResult, Needed};
+
+    use crate::engine;
+    use crate::nom::{complete, streaming};
+
+    type Result<'a> = IResult<&'a [u8], Vec<u8>, Error<&'a [u8]>>;
+
+    #[test]
+    fn complete() {
+        let result: Result = complete::base64_bytes(&engine::STANDARD)(b"SGVsbG8= rest");
+        assert_eq!(result, Ok((&b" rest"[..], b"Hello".to_vec())));
+        let result: Result = complete::base64_bytes(&engine::URL_SAFE_NO_PAD)(b"-_8");
+        assert_eq!(result, Ok((&b""[..], b"\xfb\xff".to_vec())));
+    }
+
+    #[test]
+    fn complete_missing_padding() {
+        let result: Result = complete::base64_bytes(&engine::STANDARD)(b"SGVsbG8");
+        assert!(matches!(result, Err(Err::Error(_))));
+    }
+
+    #[test]
+    fn streaming() {
+        let result: Result = streaming::base64_bytes(&engine::STANDARD)(b"SGVs");
+        assert_eq!(result, Err(Err::Incomplete(Needed::Unknown)));
+        let result: Result = streaming::base64_bytes(&engine::STANDARD)(b"SGVsbG8");
+        assert_eq!(result, Err(Err::Incomplete(Needed::Unknown)));
+        let result: Result = streaming::base64_bytes(&engine::STANDARD)(b"SGVsbA=");
+        assert_eq!(result, Err(Err::Incomplete(Needed::new(1))));
+        let result: Result = streaming::base64_bytes(&engine::STANDARD)(b"SGVsbA==,");
+        assert_eq!(result, Ok((&b","[..], b"Hell".to_vec())));
+    }
+
+    #[test]
+    fn invalid_length() {
+        let result: Result = streaming::base64_bytes(&engine::STANDARD_NO_PAD)(b"SGVsb,");
+        assert!(matches!(result, Err(Err::Error(_))));
+    }
+}
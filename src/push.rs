@@ -0,0 +1,436 @@
+use crate::engine::{self, Engine};
+use crate::tables::TABLE;
+use crate::{alphabet, decode_blocks, DecodeError};
+
+#[cfg(feature = "zeroize")]
+use ::zeroize::Zeroize;
+
+/// Symbols of a non-standard alphabet translated on the stack at once.
+const TRANSLATE_CHUNK: usize = 4 * 256;
+
+/// Push-based encoder for input that arrives in fragments.
+///
+/// Only a partial block of up to 2 bytes is kept between calls, the caller owns all
+/// buffers.
+///
+/// ```
+/// use faster_base64::Encoder;
+///
+/// let mut encoder = Encoder::new();
+/// let mut out = [0u8; 16];
+/// let (_, produced) = encoder.update(b"Hel", &mut out);
+/// let (_, produced_lo) = encoder.update(b"lo", &mut out[produced..]);
+/// let len = produced + produced_lo;
+/// let len = len + encoder.finish(&mut out[len..]);
+/// assert_eq!(&out[..len], b"SGVsbG8=");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Encoder {
+    engine: &'static Engine,
+    partial: [u8; 3],
+    partial_len: usize,
+}
+
+impl Encoder {
+    /// Creates an encoder for standard Base64 with padding.
+    pub fn new() -> Self {
+        Self::with_engine(&engine::STANDARD)
+    }
+
+    pub fn with_engine(engine: &'static Engine) -> Self {
+        Self {
+            engine,
+            partial: [0; 3],
+            partial_len: 0,
+        }
+    }
+
+    /// Encodes as much of `input` as fits into `output`.
+    ///
+    /// Returns the number of bytes consumed from `input` and written to `output`. Input is
+    /// only left unconsumed when `output` is full.
+    pub fn update(&mut self, input: &[u8], output: &mut [u8]) -> (usize, usize) {
+        let mut consumed = 0;
+        let mut produced = 0;
+
+        if self.partial_len > 0 {
+            let take = input.len().min(3 - self.partial_len);
+            self.partial[self.partial_len..self.partial_len + take].copy_from_slice(&input[..take]);
+            self.partial_len += take;
+            consumed = take;
+
+            if self.partial_len < 3 || output.len() < 4 {
+                return (consumed, produced);
+            }
+
+            produced = self.engine.encode_to_slice(&self.partial, output);
+            self.partial_len = 0;
+        }
+
+        let blocks = ((input.len() - consumed) / 3).min((output.len() - produced) / 4);
+        if blocks > 0 {
+            let span = &input[consumed..consumed + blocks * 3];
+            produced += self
+                .engine
+                .encode_to_slice(span, &mut output[produced..produced + blocks * 4]);
+            consumed += blocks * 3;
+        }
+
+        let rest = &input[consumed..];
+        if rest.len() < 3 {
+            self.partial[..rest.len()].copy_from_slice(rest);
+            self.partial_len = rest.len();
+            consumed = input.len();
+        }
+
+        (consumed, produced)
+    }
+
+    /// Encodes the partial block with padding and resets the encoder.
+    ///
+    /// Returns the number of bytes written. Panics if `output` is shorter than 4 bytes,
+    /// even when the engine does not pad.
+    pub fn finish(&mut self, output: &mut [u8]) -> usize {
+        let len = self
+            .engine
+            .encode_to_slice(&self.partial[..self.partial_len], output);
+        self.partial_len = 0;
+        len
+    }
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        self.partial.zeroize();
+    }
+}
+
+/// Push-based decoder for input that arrives in fragments.
+///
+/// Only a partial block of up to 4 symbols is kept between calls, the caller owns all
+/// buffers. Errors have offsets counted in symbols since the start of the input.
+///
+/// ```
+/// use faster_base64::Decoder;
+///
+/// let mut decoder = Decoder::new();
+/// let mut out = [0u8; 8];
+/// let (_, produced) = decoder.update(b"SGVsb", &mut out).unwrap();
+/// let (_, produced_lo) = decoder.update(b"G8=", &mut out[produced..]).unwrap();
+/// let len = produced + produced_lo;
+/// let len = len + decoder.finish(&mut out[len..]).unwrap();
+/// assert_eq!(&out[..len], b"Hello");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    engine: &'static Engine,
+    partial: [u8; 4],
+    partial_len: usize,
+    symbols_decoded: usize,
+    padding_seen: bool,
+}
+
+impl Decoder {
+    /// Creates a decoder for standard Base64 with padding.
+    pub fn new() -> Self {
+        Self::with_engine(&engine::STANDARD)
+    }
+
+    pub fn with_engine(engine: &'static Engine) -> Self {
+        Self {
+            engine,
+            partial: [0; 4],
+            partial_len: 0,
+            symbols_decoded: 0,
+            padding_seen: false,
+        }
+    }
+
+    /// Decodes as much of `input` as fits into `output`.
+    ///
+    /// Returns the number of bytes consumed from `input` and written to `output`. Input is
+    /// only left unconsumed when `output` has no room for another block.
+    pub fn update(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(usize, usize), DecodeError> {
+        let mut consumed = 0;
+        let mut produced = 0;
+
+        loop {
+            if self.partial_len == 4 {
+                if output.len() - produced < 3 {
+                    break;
+                }
+                produced += self.decode_partial(&mut output[produced..])?;
+            }
+
+            let rest = &input[consumed..];
+            if rest.is_empty() {
+                break;
+            }
+            if self.padding_seen {
+                return Err(DecodeError::InvalidByte(
+                    self.symbols_decoded + self.partial_len,
+                    rest[0],
+                ));
+            }
+
+            if self.partial_len == 0 {
+                let room = (output.len() - produced) / 3;
+                let mut blocks = (rest.len() / 4).min(room);
+                // Blocks from the first one with padding on go through `partial`
+                if let Some(i) = rest[..blocks * 4].iter().position(|&b| b == b'=') {
+                    blocks = i / 4;
+                }
+
+                if blocks > 0 {
+                    self.decode_span(&rest[..blocks * 4], &mut output[produced..])?;
+                    consumed += blocks * 4;
+                    produced += blocks * 3;
+                    continue;
+                }
+                if rest.len() >= 4 && room == 0 {
+                    break;
+                }
+            }
+
+            let take = rest.len().min(4 - self.partial_len);
+            self.partial[self.partial_len..self.partial_len + take].copy_from_slice(&rest[..take]);
+            self.partial_len += take;
+            consumed += take;
+        }
+
+        Ok((consumed, produced))
+    }
+
+    /// Decodes the partial block and resets the decoder.
+    ///
+    /// Returns the number of bytes written. Panics if `output` is shorter than the decoded
+    /// block, which is at most 3 bytes.
+    pub fn finish(&mut self, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let result = if self.partial_len > 0 {
+            self.decode_partial(output)
+        } else {
+            Ok(0)
+        };
+        self.partial_len = 0;
+        self.symbols_decoded = 0;
+        self.padding_seen = false;
+        result
+    }
+
+    /// Decodes the buffered symbols, which can include padding.
+    fn decode_partial(&mut self, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let mut block = self.partial;
+        let result = self
+            .engine
+            .decode(&mut block[..self.partial_len])
+            .map(|decoded| {
+                output[..decoded.len()].copy_from_slice(decoded);
+                decoded.len()
+            });
+        #[cfg(feature = "zeroize")]
+        block.zeroize();
+        let decoded_len = result.map_err(|err| err.offset_by(self.symbols_decoded))?;
+
+        self.padding_seen = self.partial[self.partial_len - 1] == b'=';
+        self.symbols_decoded += self.partial_len;
+        self.partial_len = 0;
+        Ok(decoded_len)
+    }
+
+    /// Decodes whole blocks without padding straight from the caller's buffer.
+    fn decode_span(&mut self, span: &[u8], output: &mut [u8]) -> Result<(), DecodeError> {
+        let alphabet = self.engine.alphabet();
+
+        if *alphabet == alphabet::STANDARD {
+            if let Some(i) = alphabet.find_invalid(span) {
+                return Err(DecodeError::InvalidByte(self.symbols_decoded + i, span[i]));
+            }
+            decode_blocks(span, output);
+        } else {
+            let mut buf = [0u8; TRANSLATE_CHUNK];
+            let result = translate_blocks(alphabet, span, &mut buf, output);
+            #[cfg(feature = "zeroize")]
+            buf.zeroize();
+            result.map_err(|err| err.offset_by(self.symbols_decoded))?;
+        }
+
+        self.symbols_decoded += span.len();
+        Ok(())
+    }
+}
+
+/// Decodes whole blocks of a non-standard alphabet by translating them through `buf`.
+fn translate_blocks(
+    alphabet: &alphabet::Alphabet,
+    span: &[u8],
+    buf: &mut [u8; TRANSLATE_CHUNK],
+    output: &mut [u8],
+) -> Result<(), DecodeError> {
+    for (n, chunk) in span.chunks(TRANSLATE_CHUNK).enumerate() {
+        for (i, (out, &symbol)) in buf.iter_mut().zip(chunk).enumerate() {
+            let value = alphabet
+                .value(symbol)
+                .ok_or(DecodeError::InvalidByte(n * TRANSLATE_CHUNK + i, symbol))?;
+            *out = TABLE[value as usize];
+        }
+        let out_pos = n * TRANSLATE_CHUNK / 4 * 3;
+        decode_blocks(&buf[..chunk.len()], &mut output[out_pos..]);
+    }
+    Ok(())
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        self.partial.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{engine, DecodeError, Decoder, Encoder};
+
+    fn encode_fragments(encoder: &mut Encoder, data: &[u8], size: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut buf = [0u8; 10];
+        for fragment in data.chunks(size) {
+            let mut fragment = fragment;
+            while !fragment.is_empty() {
+                let (consumed, produced) = encoder.update(fragment, &mut buf);
+                out.extend_from_slice(&buf[..produced]);
+                fragment = &fragment[consumed..];
+            }
+        }
+        let len = encoder.finish(&mut buf);
+        out.extend_from_slice(&buf[..len]);
+        out
+    }
+
+    fn decode_fragments(
+        decoder: &mut Decoder,
+        data: &[u8],
+        size: usize,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let mut out = Vec::new();
+        let mut buf = [0u8; 7];
+        for fragment in data.chunks(size) {
+            let mut fragment = fragment;
+            while !fragment.is_empty() {
+                let (consumed, produced) = decoder.update(fragment, &mut buf)?;
+                out.extend_from_slice(&buf[..produced]);
+                fragment = &fragment[consumed..];
+            }
+        }
+        let len = decoder.finish(&mut buf)?;
+        out.extend_from_slice(&buf[..len]);
+        Ok(out)
+    }
+
+    #[test]
+    fn encoder_fragments() {
+        let data = crate::tests::data(100);
+        // Tails of 0 to 2 bytes, in fragments that leave partial blocks between calls
+        for len in [0, 1, 2, 3, 100] {
+            for size in [1, 2, 5, 64] {
+                let out = encode_fragments(&mut Encoder::new(), &data[..len], size);
+                assert_eq!(out, crate::encode(&data[..len]).as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn encoder_engine() {
+        let mut encoder = Encoder::with_engine(&engine::URL_SAFE_NO_PAD);
+        let out = encode_fragments(&mut encoder, b"\xfb\xff\xbfHell", 2);
+        assert_eq!(out, b"-_-_SGVsbA");
+    }
+
+    #[test]
+    fn decoder_fragments() {
+        let data = crate::tests::data(100);
+        for len in [0, 1, 2, 3, 100] {
+            let encoded = crate::encode(&data[..len]);
+            for size in [1, 3, 4, 61] {
+                let out = decode_fragments(&mut Decoder::new(), encoded.as_bytes(), size);
+                assert_eq!(out, Ok(data[..len].to_vec()));
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_large_output() {
+        let data = crate::tests::data(1537);
+        // Around the symbols of TRANSLATE_CHUNK translated at once
+        for data in [&data[..767], &data[..768], &data[..769], &data] {
+            let encoded = engine::URL_SAFE.encode(data);
+            let mut decoder = Decoder::with_engine(&engine::URL_SAFE);
+            let mut out = vec![0u8; encoded.len() / 4 * 3];
+            let (consumed, produced) = decoder.update(encoded.as_bytes(), &mut out).unwrap();
+            assert_eq!(consumed, encoded.len());
+            let len = decoder.finish(&mut out[produced..]).unwrap();
+            assert_eq!(&out[..produced + len], data);
+        }
+    }
+
+    #[test]
+    fn decoder_no_pad() {
+        let mut decoder = Decoder::with_engine(&engine::STANDARD_NO_PAD);
+        assert_eq!(
+            decode_fragments(&mut decoder, b"SGVsbG8", 3),
+            Ok(b"Hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn decoder_invalid() {
+        assert_eq!(
+            decode_fragments(&mut Decoder::new(), b"SGVsbG8*SGVs", 5),
+            Err(DecodeError::InvalidByte(7, b'*'))
+        );
+        assert_eq!(
+            decode_fragments(&mut Decoder::new(), b"AAAAAAAAAAAA*AAA", 16),
+            Err(DecodeError::InvalidByte(12, b'*'))
+        );
+        assert_eq!(
+            decode_fragments(
+                &mut Decoder::with_engine(&engine::URL_SAFE),
+                b"AAAAAAAAAAAA+AAA",
+                16
+            ),
+            Err(DecodeError::InvalidByte(12, b'+'))
+        );
+    }
+
+    #[test]
+    fn decoder_data_after_padding() {
+        assert_eq!(
+            decode_fragments(&mut Decoder::new(), b"SGk=SGk=", 8),
+            Err(DecodeError::InvalidByte(4, b'S'))
+        );
+    }
+
+    #[test]
+    fn decoder_truncated() {
+        assert_eq!(
+            decode_fragments(&mut Decoder::new(), b"SGVsbG8", 8),
+            Err(DecodeError::InvalidLength(7))
+        );
+    }
+}
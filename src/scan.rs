@@ -0,0 +1,318 @@
+//! Detection of Base64 runs embedded in arbitrary text, for secret scanning and log
+//! forensics.
+//!
+//! ```
+//! use faster_base64::scan::Scanner;
+//!
+//! let text = b"token=c2VjcmV0IGtleSBtYXRlcmlhbA== user=admin";
+//! let scanner = Scanner::new().min_len(16);
+//! assert_eq!(scanner.runs(text).collect::<Vec<_>>(), vec![6..34]);
+//!
+//! let (_, decoded) = scanner.extract_and_decode(text).next().unwrap();
+//! assert_eq!(decoded, b"secret key material");
+//! ```
+
+use std::ops::Range;
+
+use crate::alphabet::{self, Alphabet};
+use crate::engine::Engine;
+
+/// Configuration for finding Base64 runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Scanner {
+    alphabet: &'static Alphabet,
+    symbols: [bool; 256],
+    padding: bool,
+    line_breaks: bool,
+    min_len: usize,
+    min_score: f64,
+}
+
+impl Scanner {
+    /// Creates a scanner for the standard alphabet with padding and without line breaks
+    /// that reports runs of at least 20 symbols.
+    pub const fn new() -> Self {
+        Self {
+            alphabet: &alphabet::STANDARD,
+            symbols: symbols(&alphabet::STANDARD),
+            padding: true,
+            line_breaks: false,
+            min_len: 20,
+            min_score: 0.0,
+        }
+    }
+
+    /// Sets the 64 symbol alphabet of the runs.
+    pub const fn alphabet(mut self, alphabet: &'static Alphabet) -> Self {
+        assert!(
+            alphabet.len() == 64,
+            "Base64 alphabets must have 64 symbols"
+        );
+        self.alphabet = alphabet;
+        self.symbols = symbols(alphabet);
+        self
+    }
+
+    /// Includes up to 2 padding symbols at the end of a run.
+    pub const fn padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Continues runs over `\n` and `\r\n` line breaks, for wrapped Base64.
+    pub const fn line_breaks(mut self, line_breaks: bool) -> Self {
+        self.line_breaks = line_breaks;
+        self
+    }
+
+    /// Sets the minimum number of symbols of a run, not counting padding and line breaks.
+    pub const fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Skips runs with a [`score`] below `min_score`.
+    pub const fn min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Returns an iterator over the byte ranges of the maximal runs in `text`.
+    pub fn runs<'a>(&self, text: &'a [u8]) -> Runs<'a> {
+        Runs {
+            scanner: *self,
+            text,
+            pos: 0,
+            mask_chunk: usize::MAX,
+            mask: 0,
+        }
+    }
+
+    /// Returns an iterator over the runs in `text` that decode, with their decoded data.
+    ///
+    /// Padding and line breaks are optional when decoding, runs that still fail to decode
+    /// are skipped.
+    pub fn extract_and_decode<'a>(
+        &self,
+        text: &'a [u8],
+    ) -> impl Iterator<Item = (Range<usize>, Vec<u8>)> + 'a {
+        let engine = Engine::new(self.alphabet, false);
+        self.runs(text).filter_map(move |range| {
+            let mut symbols = text[range.clone()]
+                .iter()
+                .copied()
+                .filter(|&b| b != b'\n' && b != b'\r' && b != b'=')
+                .collect::<Vec<_>>();
+            let len = engine.decode(&mut symbols).ok()?.len();
+            symbols.truncate(len);
+            Some((range, symbols))
+        })
+    }
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the membership table the runs are classified with.
+const fn symbols(alphabet: &Alphabet) -> [bool; 256] {
+    let mut symbols = [false; 256];
+    let mut i = 0;
+    while i < 256 {
+        symbols[i] = alphabet.value(i as u8).is_some();
+        i += 1;
+    }
+    symbols
+}
+
+/// Rates how plausible it is that `run` is encoded data rather than text, from 0 to 1.
+///
+/// This is the Shannon entropy of the symbols relative to the 6 bits a Base64 symbol can
+/// carry, padding and line breaks are ignored. Encoded random data scores above 0.85 from
+/// 64 symbols on and close to 1 for longer runs, words and repeated symbols score lower.
+pub fn score(run: &[u8]) -> f64 {
+    let mut counts = [0u32; 256];
+    let mut len = 0;
+    for &b in run {
+        if b != b'\n' && b != b'\r' && b != b'=' {
+            counts[b as usize] += 1;
+            len += 1;
+        }
+    }
+    if len < 2 {
+        return 0.0;
+    }
+
+    let entropy = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len as f64;
+            -p * p.log2()
+        })
+        .sum::<f64>();
+
+    entropy / 6.0
+}
+
+/// Iterator over Base64 runs, returned by [`Scanner::runs`].
+pub struct Runs<'a> {
+    scanner: Scanner,
+    text: &'a [u8],
+    pos: usize,
+    mask_chunk: usize,
+    mask: u64,
+}
+
+impl Runs<'_> {
+    /// Returns the symbol bitmask of the 64 byte chunk that holds `pos`.
+    fn chunk_mask(&mut self, pos: usize) -> u64 {
+        let chunk = pos / 64;
+        if chunk != self.mask_chunk {
+            let bytes = &self.text[chunk * 64..self.text.len().min(chunk * 64 + 64)];
+            self.mask = alphabet::chunk_mask(bytes, |b| self.scanner.symbols[b as usize]);
+            self.mask_chunk = chunk;
+        }
+        self.mask
+    }
+
+    /// Returns the first position from `pos` on that is (`symbol`) or is not a symbol.
+    fn find(&mut self, mut pos: usize, symbol: bool) -> usize {
+        while pos < self.text.len() {
+            let mask = self.chunk_mask(pos);
+            let mask = if symbol { mask } else { !mask };
+            let found = mask >> (pos % 64);
+            if found != 0 {
+                return self.text.len().min(pos + found.trailing_zeros() as usize);
+            }
+            pos = pos / 64 * 64 + 64;
+        }
+        self.text.len()
+    }
+
+    fn is_symbol(&self, pos: usize) -> bool {
+        self.text
+            .get(pos)
+            .is_some_and(|&b| self.scanner.symbols[b as usize])
+    }
+
+    /// Returns the length of the line break at `pos` that is followed by a symbol.
+    fn line_break(&self, pos: usize) -> Option<usize> {
+        let len = match self.text.get(pos..pos + 2) {
+            Some(b"\r\n") => 2,
+            _ if self.text.get(pos) == Some(&b'\n') => 1,
+            _ => return None,
+        };
+        self.is_symbol(pos + len).then_some(len)
+    }
+}
+
+impl Iterator for Runs<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        loop {
+            let start = self.find(self.pos, true);
+            if start == self.text.len() {
+                self.pos = start;
+                return None;
+            }
+
+            let mut end = self.find(start, false);
+            let mut symbols = end - start;
+            while self.scanner.line_breaks {
+                let Some(len) = self.line_break(end) else {
+                    break;
+                };
+                let line_end = self.find(end + len, false);
+                symbols += line_end - end - len;
+                end = line_end;
+            }
+
+            if self.scanner.padding {
+                end += self.text[end..]
+                    .iter()
+                    .take(2)
+                    .take_while(|&&b| b == b'=')
+                    .count();
+            }
+            self.pos = end;
+
+            let run = &self.text[start..end];
+            if symbols >= self.scanner.min_len && score(run) >= self.scanner.min_score {
+                return Some(start..end);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alphabet;
+    use crate::scan::{score, Scanner};
+
+    #[test]
+    fn runs() {
+        let text = b"a=SGVsbG8sIHdvcmxk b=SGk= c=SGVsbG8sIHdvcmxkIQ==!";
+        let runs = Scanner::new().min_len(8).runs(text).collect::<Vec<_>>();
+        assert_eq!(runs, vec![2..18, 28..48]);
+    }
+
+    #[test]
+    fn runs_across_chunks() {
+        let encoded = crate::encode(&[0x5a; 300]);
+        let text = format!("{}:{}.{}", "-".repeat(70), encoded, "-".repeat(70));
+        let runs = Scanner::new().runs(text.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(runs, vec![71..71 + encoded.len()]);
+    }
+
+    #[test]
+    fn runs_line_breaks() {
+        let text = b"key:\r\nSGVsbG8s\r\nIHdvcmxk\nIQ==\n\nSGVsbG8sIHdvcmxk";
+        let runs = Scanner::new()
+            .min_len(8)
+            .line_breaks(true)
+            .runs(text)
+            .collect::<Vec<_>>();
+        assert_eq!(runs, vec![6..29, 31..47]);
+    }
+
+    #[test]
+    fn runs_url_safe() {
+        let text = b"/path?t=-_-_SGVsbG8sIHdvcmxk&x=1";
+        let scanner = Scanner::new()
+            .alphabet(&alphabet::URL_SAFE)
+            .padding(false)
+            .min_len(8);
+        assert_eq!(scanner.runs(text).collect::<Vec<_>>(), vec![8..28]);
+    }
+
+    #[test]
+    fn score_filter() {
+        assert!(score(b"aaaaaaaaaaaaaaaaaaaa") < 0.1);
+        assert!(score(b"ThisIsJustALongIdentifierName") < 0.75);
+        let encoded = crate::encode(&(0..48).map(|i| (i * 73) as u8).collect::<Vec<_>>());
+        assert!(score(encoded.as_bytes()) > 0.85);
+
+        let text = format!("aaaaaaaaaaaaaaaaaaaaaaaa {}", encoded);
+        let runs = Scanner::new()
+            .min_score(0.75)
+            .runs(text.as_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(runs, vec![25..25 + encoded.len()]);
+    }
+
+    #[test]
+    fn extract_and_decode() {
+        let text = b"x SGVsbG8sIHdvcmxk\nIQ== y QUJD\r\nREVG z";
+        let decoded = Scanner::new()
+            .min_len(4)
+            .line_breaks(true)
+            .extract_and_decode(text)
+            .map(|(_, data)| data)
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, [b"Hello, world!".to_vec(), b"ABCDEF".to_vec()]);
+    }
+}
@@ -0,0 +1,229 @@
+//! Serializes byte fields as Base64 strings, for use with `#[serde(with = "...")]`.
+//!
+//! Fields can be any type that implements `AsRef<[u8]>` and `TryFrom<Vec<u8>>`, such as
+//! `Vec<u8>`, `[u8; N]` or `bytes::Bytes`. `Option` fields use the `option` submodules.
+//!
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Token {
+//!     #[serde(with = "faster_base64::serde::url_safe")]
+//!     key: Vec<u8>,
+//!     #[serde(with = "faster_base64::serde::standard::option")]
+//!     digest: Option<[u8; 4]>,
+//! }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::Serializer;
+
+use crate::engine::Engine;
+
+fn serialize<S: Serializer>(
+    engine: &Engine,
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&engine.encode(bytes))
+}
+
+fn deserialize<'de, T, D>(engine: &'static Engine, deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(BytesVisitor {
+        engine,
+        marker: PhantomData,
+    })
+}
+
+struct BytesVisitor<T> {
+    engine: &'static Engine,
+    marker: PhantomData<T>,
+}
+
+impl<T: TryFrom<Vec<u8>>> BytesVisitor<T> {
+    /// Decodes `buf` in-place, so owned strings from the deserializer are never copied.
+    fn decode<E: de::Error>(&self, mut buf: Vec<u8>) -> Result<T, E> {
+        let len = self.engine.decode(&mut buf).map_err(E::custom)?.len();
+        buf.truncate(len);
+        T::try_from(buf).map_err(|_| E::invalid_length(len, self))
+    }
+}
+
+impl<T: TryFrom<Vec<u8>>> Visitor<'_> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a Base64 string of the expected length")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        self.decode(v.as_bytes().to_vec())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<T, E> {
+        self.decode(v.into_bytes())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        self.decode(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+        self.decode(v)
+    }
+}
+
+struct OptionVisitor<T> {
+    engine: &'static Engine,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> Visitor<'de> for OptionVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an optional Base64 string")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+        deserialize(self.engine, deserializer).map(Some)
+    }
+}
+
+macro_rules! engine_module {
+    ($(#[$doc:meta])* $name:ident, $engine:path) => {
+        $(#[$doc])*
+        pub mod $name {
+            use ::serde::{Deserializer, Serializer};
+
+            pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: AsRef<[u8]> + ?Sized,
+                S: Serializer,
+            {
+                super::serialize(&$engine, bytes.as_ref(), serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: TryFrom<Vec<u8>>,
+                D: Deserializer<'de>,
+            {
+                super::deserialize(&$engine, deserializer)
+            }
+
+            /// Same as the parent module, for `Option` fields.
+            pub mod option {
+                use ::serde::{Deserializer, Serializer};
+
+                pub fn serialize<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    T: AsRef<[u8]>,
+                    S: Serializer,
+                {
+                    match bytes {
+                        Some(bytes) => serializer.serialize_some(&$engine.encode(bytes.as_ref())),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+                where
+                    T: TryFrom<Vec<u8>>,
+                    D: Deserializer<'de>,
+                {
+                    deserializer.deserialize_option(super::super::OptionVisitor {
+                        engine: &$engine,
+                        marker: std::marker::PhantomData,
+                    })
+                }
+            }
+        }
+    };
+}
+
+engine_module!(
+    /// Standard Base64 with padding.
+    standard,
+    crate::engine::STANDARD
+);
+
+engine_module!(
+    /// Standard Base64 without padding.
+    standard_no_pad,
+    crate::engine::STANDARD_NO_PAD
+);
+
+engine_module!(
+    /// URL and filename safe Base64 with padding.
+    url_safe,
+    crate::engine::URL_SAFE
+);
+
+engine_module!(
+    /// URL and filename safe Base64 without padding.
+    url_safe_no_pad,
+    crate::engine::URL_SAFE_NO_PAD
+);
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Message {
+        #[serde(with = "crate::serde::standard")]
+        body: Vec<u8>,
+        #[serde(with = "crate::serde::url_safe_no_pad")]
+        key: [u8; 3],
+        #[serde(with = "crate::serde::standard_no_pad::option")]
+        digest: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn serialize() {
+        let message = Message {
+            body: b"Hello".to_vec(),
+            key: *b"\xfb\xff\xbf",
+            digest: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"body":"SGVsbG8=","key":"-_-_","digest":null}"#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let message: Message =
+            serde_json::from_str(r#"{"body":"SGVsbG8=","key":"-_-_","digest":"SGk"}"#).unwrap();
+        assert_eq!(
+            message,
+            Message {
+                body: b"Hello".to_vec(),
+                key: *b"\xfb\xff\xbf",
+                digest: Some(b"Hi".to_vec()),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_wrong_length() {
+        let result =
+            serde_json::from_str::<Message>(r#"{"body":"","key":"SGVsbG8","digest":null}"#);
+        assert!(result.is_err());
+    }
+}
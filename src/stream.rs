@@ -0,0 +1,816 @@
+//! Streaming adapters for `io::Write` and `io::Read`.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::{Deref, DerefMut, Range};
+
+#[cfg(feature = "zeroize")]
+use ::zeroize::Zeroize;
+
+use crate::engine::{self, Engine};
+use crate::{DecodeError, DecodeLimits};
+
+/// Input bytes encoded per call of the encoding kernel.
+const ENCODE_CHUNK: usize = 3 * 16 * 1024;
+
+/// Encoded bytes read from the inner reader at once.
+const DECODE_CHUNK: usize = 4 * 16 * 1024;
+
+/// Heap buffer of stream data, wiped on drop with the `zeroize` feature.
+struct Buffer(Vec<u8>);
+
+impl Buffer {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len])
+    }
+}
+
+impl Deref for Buffer {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Buffer {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Encodes everything written to it and writes the result to the inner writer.
+///
+/// Call [`finish`](EncoderWriter::finish) to write the last partial chunk and padding,
+/// otherwise it is written on drop with errors ignored.
+pub struct EncoderWriter<W: Write> {
+    inner: Option<W>,
+    engine: &'static Engine,
+    pending: [u8; 3],
+    pending_len: usize,
+    out: Buffer,
+}
+
+impl<W: Write> EncoderWriter<W> {
+    /// Creates a writer that encodes standard Base64 with padding.
+    pub fn new(inner: W) -> Self {
+        Self::with_engine(inner, &engine::STANDARD)
+    }
+
+    pub fn with_engine(inner: W, engine: &'static Engine) -> Self {
+        Self {
+            inner: Some(inner),
+            engine,
+            pending: [0; 3],
+            pending_len: 0,
+            out: Buffer::new(ENCODE_CHUNK / 3 * 4),
+        }
+    }
+
+    /// Writes the last partial chunk and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_pending()?;
+        let mut inner = self.inner.take().unwrap();
+        inner.flush()?;
+        Ok(inner)
+    }
+
+    fn write_pending(&mut self) -> io::Result<()> {
+        if self.pending_len > 0 {
+            let len = self
+                .engine
+                .encode_to_slice(&self.pending[..self.pending_len], &mut self.out[..]);
+            self.pending_len = 0;
+            self.inner.as_mut().unwrap().write_all(&self.out[..len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncoderWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = self.inner.as_mut().unwrap();
+        let mut data = buf;
+
+        if self.pending_len > 0 {
+            let take = data.len().min(3 - self.pending_len);
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&data[..take]);
+            self.pending_len += take;
+            data = &data[take..];
+
+            if self.pending_len < 3 {
+                return Ok(buf.len());
+            }
+
+            let len = self
+                .engine
+                .encode_to_slice(&self.pending, &mut self.out[..]);
+            self.pending_len = 0;
+            inner.write_all(&self.out[..len])?;
+        }
+
+        let whole = data.len() / 3 * 3;
+        for chunk in data[..whole].chunks(ENCODE_CHUNK) {
+            let len = self.engine.encode_to_slice(chunk, &mut self.out[..]);
+            inner.write_all(&self.out[..len])?;
+        }
+
+        let rest = &data[whole..];
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for EncoderWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_pending();
+        }
+        #[cfg(feature = "zeroize")]
+        self.pending.zeroize();
+    }
+}
+
+/// Decodes Base64 read from the inner reader.
+///
+/// `\n` line breaks are always skipped, `\r` only with
+/// [`ignore_garbage`](DecoderReader::ignore_garbage) like in GNU `base64`. Invalid input is
+/// reported as an error of kind `InvalidData` wrapping a [`DecodeError`], with offsets counted
+/// in symbols. The data decoded before invalid input is read first, and every read after
+/// the error fails again.
+pub struct DecoderReader<R: Read> {
+    inner: R,
+    engine: &'static Engine,
+    ignore_garbage: bool,
+    limits: DecodeLimits,
+    input_read: usize,
+    line_len: usize,
+    decoded_len: usize,
+    input: Buffer,
+    input_len: usize,
+    out: Buffer,
+    out_pos: usize,
+    out_len: usize,
+    symbols_decoded: usize,
+    eof: bool,
+    error: Option<DecodeError>,
+}
+
+impl<R: Read> DecoderReader<R> {
+    /// Creates a reader that decodes standard Base64 with padding.
+    pub fn new(inner: R) -> Self {
+        Self::with_engine(inner, &engine::STANDARD)
+    }
+
+    pub fn with_engine(inner: R, engine: &'static Engine) -> Self {
+        Self {
+            inner,
+            engine,
+            ignore_garbage: false,
+            limits: DecodeLimits::new(),
+            input_read: 0,
+            line_len: 0,
+            decoded_len: 0,
+            input: Buffer::new(DECODE_CHUNK),
+            input_len: 0,
+            out: Buffer::new(DECODE_CHUNK / 4 * 3),
+            out_pos: 0,
+            out_len: 0,
+            symbols_decoded: 0,
+            eof: false,
+            error: None,
+        }
+    }
+
+    /// Skips every byte that is not part of the alphabet or padding, instead of only line
+    /// breaks.
+    pub fn ignore_garbage(mut self, ignore_garbage: bool) -> Self {
+        self.ignore_garbage = ignore_garbage;
+        self
+    }
+
+    /// Fails reading with an error wrapping [`DecodeError::LimitExceeded`] as soon as the
+    /// input exceeds `limits`, and every read after that.
+    ///
+    /// Line lengths count every byte between `\n` line breaks, garbage included.
+    pub fn limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and decodes until there is decoded output or the input ended.
+    fn fill(&mut self) -> io::Result<()> {
+        while self.out_pos == self.out_len && !self.eof && self.error.is_none() {
+            let read = self.inner.read(&mut self.input[self.input_len..])?;
+            if read == 0 {
+                self.eof = true;
+                self.decode_input(self.input_len)?;
+                break;
+            }
+
+            let start = self.input_len;
+            self.check_input(start..start + read)?;
+            if self.ignore_garbage {
+                for i in start..start + read {
+                    let byte = self.input[i];
+                    if byte == b'=' || self.engine.alphabet().value(byte).is_some() {
+                        self.input[self.input_len] = byte;
+                        self.input_len += 1;
+                    }
+                }
+            } else {
+                // Move whole lines at once, line breaks are the only bytes dropped
+                let mut pos = start;
+                while pos < start + read {
+                    let line_len = self.input[pos..start + read]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .unwrap_or(start + read - pos);
+                    self.input.copy_within(pos..pos + line_len, self.input_len);
+                    self.input_len += line_len;
+                    pos += line_len + 1;
+                }
+            }
+
+            self.decode_input(self.input_len / 4 * 4)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the input length and line lengths with newly read bytes at `range`.
+    fn check_input(&mut self, range: Range<usize>) -> io::Result<()> {
+        self.input_read += range.len();
+        if self.input_read > self.limits.max_input_len {
+            return Err(self.limit_exceeded());
+        }
+
+        if self.limits.max_line_len != usize::MAX {
+            for &byte in &self.input[range] {
+                if byte == b'\n' {
+                    self.line_len = 0;
+                } else {
+                    self.line_len += 1;
+                    if self.line_len > self.limits.max_line_len {
+                        return Err(self.limit_exceeded());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails every later read as well, so input past a limit is never decoded.
+    fn limit_exceeded(&mut self) -> io::Error {
+        self.error = Some(DecodeError::LimitExceeded);
+        invalid_data(DecodeError::LimitExceeded)
+    }
+
+    /// Decodes the first `len` buffered symbols into the output buffer.
+    ///
+    /// Like GNU `base64`, padding only ends its block, so concatenated padded input decodes
+    /// to the concatenated data, and the symbols before invalid input are still decoded.
+    fn decode_input(&mut self, len: usize) -> io::Result<()> {
+        let mut pos = 0;
+        let mut out_len = 0;
+
+        while pos < len {
+            let end = match self.input[pos..len].iter().position(|&b| b == b'=') {
+                Some(i) => len.min(pos + i / 4 * 4 + 4),
+                None => len,
+            };
+
+            let offset = self.symbols_decoded + pos;
+            let decoded = match self.engine.decode(&mut self.input[pos..end]) {
+                Ok(decoded) => decoded,
+                Err(err) => {
+                    let symbols = match err {
+                        DecodeError::InvalidByte(i, _) => i,
+                        _ => self.input[pos..end]
+                            .iter()
+                            .position(|&b| b == b'=')
+                            .unwrap_or(end - pos),
+                    };
+                    // A single symbol of a block carries no complete byte
+                    let symbols = symbols - usize::from(symbols % 4 == 1);
+                    self.error = Some(err.offset_by(offset));
+                    Engine::new(self.engine.alphabet(), false)
+                        .decode(&mut self.input[pos..pos + symbols])
+                        .expect("symbols before the error are valid")
+                }
+            };
+
+            self.decoded_len += decoded.len();
+            if self.decoded_len > self.limits.max_decoded_len {
+                return Err(self.limit_exceeded());
+            }
+            self.out[out_len..out_len + decoded.len()].copy_from_slice(decoded);
+            out_len += decoded.len();
+            pos = end;
+            if self.error.is_some() {
+                break;
+            }
+        }
+
+        self.out_pos = 0;
+        self.out_len = out_len;
+
+        self.input.copy_within(len..self.input_len, 0);
+        self.input_len -= len;
+        self.symbols_decoded += len;
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecoderReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill()?;
+        if self.out_pos == self.out_len {
+            if let Some(err) = self.error {
+                return Err(invalid_data(err));
+            }
+        }
+
+        let len = buf.len().min(self.out_len - self.out_pos);
+        buf[..len].copy_from_slice(&self.out[self.out_pos..self.out_pos + len]);
+        self.out_pos += len;
+
+        Ok(len)
+    }
+}
+
+/// Random access to the decoded data of Base64 in a seekable reader.
+///
+/// A decoded offset maps to a known encoded offset, so seeking only reads and decodes the
+/// blocks around the new position. Input can be wrapped into lines of a fixed number of
+/// symbols, separated by `\n` or `\r\n`.
+pub struct SeekableDecoder<R: Read + Seek> {
+    inner: R,
+    engine: &'static Engine,
+    line_len: Option<u64>,
+    line_ending_len: u64,
+    symbols: u64,
+    decoded_len: u64,
+    pos: u64,
+    input: Buffer,
+    out: Buffer,
+    out_start: u64,
+    out_len: usize,
+}
+
+impl<R: Read + Seek> SeekableDecoder<R> {
+    /// Creates a reader over standard Base64 with padding on a single line.
+    pub fn new(inner: R) -> io::Result<Self> {
+        Self::with_engine(inner, &engine::STANDARD)
+    }
+
+    pub fn with_engine(inner: R, engine: &'static Engine) -> io::Result<Self> {
+        Self::open(inner, engine, None)
+    }
+
+    /// Creates a reader over Base64 wrapped into lines of `line_len` symbols, like the
+    /// output of `base64` or `fb64`.
+    pub fn with_line_len(inner: R, engine: &'static Engine, line_len: usize) -> io::Result<Self> {
+        assert!(line_len > 0, "line length must not be zero");
+        Self::open(inner, engine, Some(line_len as u64))
+    }
+
+    fn open(mut inner: R, engine: &'static Engine, line_len: Option<u64>) -> io::Result<Self> {
+        let mut len = inner.seek(SeekFrom::End(0))?;
+
+        let mut line_ending_len = 1;
+        if let Some(line_len) = line_len.filter(|&line_len| len > line_len) {
+            if read_byte(&mut inner, line_len)? == b'\r' {
+                line_ending_len = 2;
+            }
+        }
+        while len > 0 && matches!(read_byte(&mut inner, len - 1)?, b'\n' | b'\r') {
+            len -= 1;
+        }
+
+        // Sized once for the most line breaks a chunk can span, so it is never reallocated
+        let line_breaks = line_len.map_or(0, |line_len| DECODE_CHUNK as u64 / line_len + 1);
+        let input_len = DECODE_CHUNK + (line_breaks * line_ending_len) as usize;
+
+        let mut decoder = Self {
+            inner,
+            engine,
+            line_len,
+            line_ending_len,
+            symbols: len,
+            decoded_len: 0,
+            pos: 0,
+            input: Buffer::new(input_len),
+            out: Buffer::new(DECODE_CHUNK / 4 * 3),
+            out_start: 0,
+            out_len: 0,
+        };
+
+        if let Some(line_len) = line_len {
+            let stride = line_len + line_ending_len;
+            if len % stride > line_len {
+                return Err(invalid_line_breaks());
+            }
+            decoder.symbols = len / stride * line_len + len % stride;
+        }
+
+        let mut padding = 0;
+        while padding < 2.min(decoder.symbols) {
+            let offset = decoder.encoded_offset(decoder.symbols - padding - 1);
+            if read_byte(&mut decoder.inner, offset)? != b'=' {
+                break;
+            }
+            padding += 1;
+        }
+
+        if engine.padding() && !decoder.symbols.is_multiple_of(4) {
+            return Err(invalid_data(DecodeError::InvalidLength(
+                decoder.symbols as usize,
+            )));
+        }
+        let unpadded = decoder.symbols - padding;
+        if unpadded % 4 == 1 {
+            return Err(invalid_data(DecodeError::InvalidLength(unpadded as usize)));
+        }
+        // Whole blocks are counted in u64, which usize may not hold
+        decoder.decoded_len = unpadded / 4 * 3 + crate::decoded_len((unpadded % 4) as usize) as u64;
+
+        Ok(decoder)
+    }
+
+    /// Returns the length of the decoded data.
+    pub fn decoded_len(&self) -> u64 {
+        self.decoded_len
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Maps a symbol index to its offset in the inner reader.
+    fn encoded_offset(&self, symbol: u64) -> u64 {
+        match self.line_len {
+            Some(line_len) => symbol + symbol / line_len * self.line_ending_len,
+            None => symbol,
+        }
+    }
+
+    /// Decodes the chunk of blocks that starts with the block at the current position.
+    fn fill(&mut self) -> io::Result<()> {
+        let block = self.pos / 3;
+        let first = block * 4;
+        let last = self.symbols.min(first + DECODE_CHUNK as u64);
+
+        let start = self.encoded_offset(first);
+        let mut len = (self.encoded_offset(last - 1) + 1 - start) as usize;
+        self.inner.seek(SeekFrom::Start(start))?;
+        self.inner.read_exact(&mut self.input[..len])?;
+
+        if self.line_len.is_some() {
+            let mut symbols = 0;
+            for i in 0..len {
+                let byte = self.input[i];
+                if byte != b'\n' && byte != b'\r' {
+                    self.input[symbols] = byte;
+                    symbols += 1;
+                }
+            }
+            if symbols as u64 != last - first {
+                return Err(invalid_line_breaks());
+            }
+            len = symbols;
+        }
+
+        let decoded = self
+            .engine
+            .decode(&mut self.input[..len])
+            .map_err(|err| invalid_data(err.offset_by(first as usize)))?;
+        self.out[..decoded.len()].copy_from_slice(decoded);
+        self.out_start = block * 3;
+        self.out_len = decoded.len();
+
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Read for SeekableDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.decoded_len {
+            return Ok(0);
+        }
+        if self.pos < self.out_start || self.pos >= self.out_start + self.out_len as u64 {
+            self.fill()?;
+        }
+
+        let out_pos = (self.pos - self.out_start) as usize;
+        let len = buf.len().min(self.out_len - out_pos);
+        buf[..len].copy_from_slice(&self.out[out_pos..out_pos + len]);
+        self.pos += len as u64;
+
+        Ok(len)
+    }
+}
+
+impl<R: Read + Seek> Seek for SeekableDecoder<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(offset) => self.decoded_len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        self.pos = pos.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.pos)
+    }
+}
+
+fn read_byte(reader: &mut (impl Read + Seek), offset: u64) -> io::Result<u8> {
+    let mut byte = [0];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn invalid_line_breaks() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "line breaks do not match the line length",
+    )
+}
+
+fn invalid_data(err: DecodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+    use crate::engine;
+    use crate::stream::{
+        DecoderReader, EncoderWriter, SeekableDecoder, DECODE_CHUNK, ENCODE_CHUNK,
+    };
+    use crate::{DecodeError, DecodeLimits};
+
+    #[test]
+    fn encoder_writer() {
+        let mut writer = EncoderWriter::new(Vec::new());
+        writer.write_all(b"He").unwrap();
+        writer.write_all(b"l").unwrap();
+        writer.write_all(b"lo").unwrap();
+        assert_eq!(writer.finish().unwrap(), b"SGVsbG8=");
+    }
+
+    #[test]
+    fn encoder_writer_large() {
+        let data = crate::tests::data(ENCODE_CHUNK + 1);
+        // Tails of 0, 1 and 2 bytes around a full buffer, in writes that split blocks
+        for len in [ENCODE_CHUNK - 1, ENCODE_CHUNK, ENCODE_CHUNK + 1] {
+            let mut writer = EncoderWriter::with_engine(Vec::new(), &engine::URL_SAFE_NO_PAD);
+            for chunk in data[..len].chunks(1001) {
+                writer.write_all(chunk).unwrap();
+            }
+            let out = writer.finish().unwrap();
+            assert_eq!(out, engine::URL_SAFE_NO_PAD.encode(&data[..len]).as_bytes());
+        }
+    }
+
+    #[test]
+    fn decoder_reader() {
+        let mut out = Vec::new();
+        DecoderReader::new(&b"SGVs\nbG8=\n"[..])
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, b"Hello");
+    }
+
+    #[test]
+    fn decoder_reader_carriage_return() {
+        let err = DecoderReader::new(&b"SGVs\r\nbG8=\r\n"[..])
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<DecodeError>().unwrap(),
+            Box::new(DecodeError::InvalidByte(4, b'\r'))
+        );
+
+        let mut out = Vec::new();
+        DecoderReader::new(&b"SGVs\r\nbG8=\r\n"[..])
+            .ignore_garbage(true)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, b"Hello");
+    }
+
+    #[test]
+    fn decoder_reader_large() {
+        let len = DECODE_CHUNK / 4 * 3;
+        let data = crate::tests::data(len + 1);
+        // Tails of 0, 1 and 2 bytes around a full buffer, with and without line breaks
+        for len in [len - 1, len, len + 1] {
+            let encoded = crate::encode(&data[..len]);
+            for encoded in [encoded.clone(), wrap(&encoded, 76, "\n")] {
+                let mut out = Vec::new();
+                DecoderReader::new(encoded.as_bytes())
+                    .read_to_end(&mut out)
+                    .unwrap();
+                assert_eq!(out, data[..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_reader_ignore_garbage() {
+        let mut out = Vec::new();
+        DecoderReader::new(&b"SG*Vs bG\x008="[..])
+            .ignore_garbage(true)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, b"Hello");
+    }
+
+    #[test]
+    fn decoder_reader_invalid() {
+        let err = DecoderReader::new(&b"SGVs\nbG8*"[..])
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<DecodeError>().unwrap(),
+            Box::new(DecodeError::InvalidByte(7, b'*'))
+        );
+    }
+
+    #[test]
+    fn decoder_reader_prefix_before_error() {
+        let mut reader = DecoderReader::new(&b"SGVsbG8gV2*y\nbGQ="[..]);
+        let mut out = Vec::new();
+        let err = reader.read_to_end(&mut out).unwrap_err();
+        assert_eq!(out, b"Hello W");
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<DecodeError>().unwrap(),
+            Box::new(DecodeError::InvalidByte(10, b'*'))
+        );
+        assert!(reader.read(&mut [0; 16]).is_err());
+
+        let mut out = Vec::new();
+        DecoderReader::new(&b"SGVsbG8gWQ"[..])
+            .read_to_end(&mut out)
+            .unwrap_err();
+        assert_eq!(out, b"Hello Y");
+    }
+
+    #[test]
+    fn decoder_reader_concatenated() {
+        let mut out = Vec::new();
+        DecoderReader::new(&b"YQ==\nYg==\nYQ==YWJj"[..])
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, b"abaabc");
+    }
+
+    #[test]
+    fn decoder_reader_limits() {
+        let encoded = wrap(&crate::encode(&[0x5a; 3000]), 76, "\n");
+        let read = |limits| {
+            DecoderReader::new(encoded.as_bytes())
+                .limits(limits)
+                .read_to_end(&mut Vec::new())
+                .map_err(|err| *err.into_inner().unwrap().downcast::<DecodeError>().unwrap())
+        };
+
+        let limits = DecodeLimits::new()
+            .max_decoded_len(3000)
+            .max_line_len(76)
+            .max_input_len(encoded.len());
+        assert_eq!(read(limits), Ok(3000));
+        assert_eq!(
+            read(limits.max_decoded_len(2999)),
+            Err(DecodeError::LimitExceeded)
+        );
+        assert_eq!(
+            read(limits.max_line_len(75)),
+            Err(DecodeError::LimitExceeded)
+        );
+        assert_eq!(
+            read(limits.max_input_len(encoded.len() - 1)),
+            Err(DecodeError::LimitExceeded)
+        );
+    }
+
+    #[test]
+    fn decoder_reader_limit_is_sticky() {
+        let input = (&b"YWJj\n"[..])
+            .chain(&b"WFhYWFhYWFhY\n"[..])
+            .chain(&b"\nZGVm\n"[..]);
+        let mut reader = DecoderReader::new(input).limits(DecodeLimits::new().max_line_len(8));
+        let mut buf = [0u8; 16];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], b"abc");
+        // Retrying must not skip the over-long line and decode what follows
+        for _ in 0..2 {
+            let err = reader.read(&mut buf).unwrap_err();
+            assert_eq!(
+                err.into_inner().unwrap().downcast::<DecodeError>().unwrap(),
+                Box::new(DecodeError::LimitExceeded)
+            );
+        }
+    }
+
+    fn wrap(encoded: &str, line_len: usize, line_ending: &str) -> String {
+        let mut out = String::new();
+        for line in encoded.as_bytes().chunks(line_len) {
+            out.push_str(std::str::from_utf8(line).unwrap());
+            out.push_str(line_ending);
+        }
+        out
+    }
+
+    #[test]
+    fn seekable_decoder() {
+        let data = crate::tests::data(1001);
+        // Tails of 0, 1 and 2 bytes, read from every position within a block
+        for len in [999, 1000, 1001] {
+            let encoded = crate::encode(&data[..len]);
+            let mut decoder = SeekableDecoder::new(Cursor::new(encoded)).unwrap();
+            assert_eq!(decoder.decoded_len(), len as u64);
+
+            for start in [0, 1, 2, 500, len - 2, len - 1, len] {
+                decoder.seek(SeekFrom::Start(start as u64)).unwrap();
+                let mut out = Vec::new();
+                decoder.read_to_end(&mut out).unwrap();
+                assert_eq!(out, data[start..len]);
+            }
+
+            decoder.seek(SeekFrom::End(-50)).unwrap();
+            let mut buf = [0u8; 50];
+            decoder.read_exact(&mut buf).unwrap();
+            assert_eq!(buf, data[len - 50..len]);
+        }
+    }
+
+    #[test]
+    fn seekable_decoder_wrapped() {
+        let data = crate::tests::data(100000);
+        for (line_ending, len) in [("\n", 100000), ("\r\n", 99998)] {
+            let encoded = wrap(&crate::encode(&data[..len]), 76, line_ending);
+            let mut decoder =
+                SeekableDecoder::with_line_len(Cursor::new(encoded), &engine::STANDARD, 76)
+                    .unwrap();
+            assert_eq!(decoder.decoded_len(), len as u64);
+
+            let mut buf = [0u8; 1000];
+            decoder.seek(SeekFrom::Start(77777)).unwrap();
+            decoder.read_exact(&mut buf).unwrap();
+            assert_eq!(buf, data[77777..78777]);
+
+            decoder.seek(SeekFrom::Start(0)).unwrap();
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out).unwrap();
+            assert_eq!(out, data[..len]);
+        }
+    }
+
+    #[test]
+    fn seekable_decoder_no_pad() {
+        let encoded = engine::URL_SAFE_NO_PAD.encode(b"Hello, world");
+        let mut decoder =
+            SeekableDecoder::with_engine(Cursor::new(encoded), &engine::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(decoder.decoded_len(), 12);
+
+        let mut out = String::new();
+        decoder.seek(SeekFrom::Start(7)).unwrap();
+        decoder.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "world");
+    }
+
+    #[test]
+    fn seekable_decoder_invalid() {
+        let mut decoder = SeekableDecoder::new(Cursor::new("SGVsbG8*SGVsbG8=")).unwrap();
+        decoder.seek(SeekFrom::Start(3)).unwrap();
+        let err = decoder.read(&mut [0; 4]).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<DecodeError>().unwrap(),
+            Box::new(DecodeError::InvalidByte(7, b'*'))
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{validate_canonical, DecodeError};
+
+/// Borrowed string that is known to hold canonical, padded standard Base64.
+///
+/// Validation happens once on construction, so decoding can not fail. Because only the
+/// canonical encoding is accepted, two values are equal exactly when their decoded bytes
+/// are equal.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Base64Str(str);
+
+/// Owned string that is known to hold canonical, padded standard Base64.
+///
+/// Dereferences to [`Base64Str`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base64String(String);
+
+impl Base64Str {
+    /// Validates `s` and wraps it without copying.
+    pub fn new(s: &str) -> Result<&Base64Str, DecodeError> {
+        validate_canonical(s.as_bytes())?;
+        Ok(unsafe { Self::from_str_unchecked(s) })
+    }
+
+    /// # Safety
+    ///
+    /// `s` must be canonical, padded standard Base64.
+    unsafe fn from_str_unchecked(s: &str) -> &Base64Str {
+        &*(s as *const str as *const Base64Str)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the exact length of the decoded data.
+    pub fn decoded_len(&self) -> usize {
+        let padding = self.0.bytes().rev().take_while(|&b| b == b'=').count();
+        self.0.len() / 4 * 3 - padding
+    }
+
+    pub fn decode(&self) -> Vec<u8> {
+        let mut out = self.0.as_bytes().to_vec();
+        let len = crate::decode(&mut out).len();
+        out.truncate(len);
+        out
+    }
+}
+
+impl Base64String {
+    /// Validates `s` and takes ownership without copying.
+    pub fn new(s: String) -> Result<Self, DecodeError> {
+        Base64Str::new(&s)?;
+        Ok(Self(s))
+    }
+
+    /// Encodes `data`, which always produces a valid value.
+    pub fn encode(data: &[u8]) -> Self {
+        Self(crate::encode(data))
+    }
+
+    pub fn as_base64_str(&self) -> &Base64Str {
+        unsafe { Base64Str::from_str_unchecked(&self.0) }
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for Base64Str {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Base64String {
+    type Target = Base64Str;
+
+    fn deref(&self) -> &Base64Str {
+        self.as_base64_str()
+    }
+}
+
+impl AsRef<str> for Base64Str {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Base64String {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<Base64Str> for Base64String {
+    fn as_ref(&self) -> &Base64Str {
+        self.as_base64_str()
+    }
+}
+
+impl Borrow<Base64Str> for Base64String {
+    fn borrow(&self) -> &Base64Str {
+        self.as_base64_str()
+    }
+}
+
+impl ToOwned for Base64Str {
+    type Owned = Base64String;
+
+    fn to_owned(&self) -> Base64String {
+        Base64String(self.0.to_string())
+    }
+}
+
+impl FromStr for Base64String {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        Base64Str::new(s).map(ToOwned::to_owned)
+    }
+}
+
+impl TryFrom<String> for Base64String {
+    type Error = DecodeError;
+
+    fn try_from(s: String) -> Result<Self, DecodeError> {
+        Self::new(s)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a Base64Str {
+    type Error = DecodeError;
+
+    fn try_from(s: &'a str) -> Result<Self, DecodeError> {
+        Base64Str::new(s)
+    }
+}
+
+impl From<Base64String> for String {
+    fn from(s: Base64String) -> String {
+        s.0
+    }
+}
+
+impl fmt::Display for Base64Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Base64String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Base64Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Base64String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq<str> for Base64Str {
+    fn eq(&self, other: &str) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Base64Str {
+    fn eq(&self, other: &&str) -> bool {
+        &self.0 == *other
+    }
+}
+
+impl PartialEq<str> for Base64String {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Base64String {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Base64Str> for Base64String {
+    fn eq(&self, other: &Base64Str) -> bool {
+        self.as_base64_str() == other
+    }
+}
+
+impl PartialEq<&Base64Str> for Base64String {
+    fn eq(&self, other: &&Base64Str) -> bool {
+        self.as_base64_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Base64Str, Base64String, DecodeError};
+
+    #[test]
+    fn new() {
+        let s = Base64Str::new("SGVsbG8=").unwrap();
+        assert_eq!(s, "SGVsbG8=");
+        assert_eq!(s.decoded_len(), 5);
+        assert_eq!(s.decode(), b"Hello");
+    }
+
+    #[test]
+    fn new_invalid() {
+        assert_eq!(
+            Base64Str::new("SGVsbG9="),
+            Err(DecodeError::InvalidLastSymbol(6, b'9'))
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let s: Base64String = "SGk=".parse().unwrap();
+        assert_eq!(s.decode(), b"Hi");
+        assert_eq!(s.to_string(), "SGk=");
+        assert_eq!(s.len(), 4);
+    }
+
+    #[test]
+    fn encode() {
+        let s = Base64String::encode(b"Hello");
+        assert_eq!(s, Base64Str::new("SGVsbG8=").unwrap());
+        assert_eq!(s.decoded_len(), 5);
+    }
+
+    #[test]
+    fn empty() {
+        let s = Base64Str::new("").unwrap();
+        assert_eq!(s.decoded_len(), 0);
+        assert_eq!(s.decode(), b"");
+    }
+}
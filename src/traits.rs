@@ -0,0 +1,171 @@
+use std::borrow::Cow;
+
+use crate::engine::{self, Engine};
+use crate::DecodeError;
+
+/// Encodes a value as Base64.
+///
+/// ```
+/// use faster_base64::engine;
+/// use faster_base64::ToBase64;
+///
+/// assert_eq!(b"Hello".to_base64(), "SGVsbG8=");
+/// assert_eq!("Hello".to_base64_with(&engine::STANDARD_NO_PAD), "SGVsbG8");
+/// ```
+pub trait ToBase64 {
+    /// Encodes as standard Base64 with padding.
+    fn to_base64(&self) -> String {
+        self.to_base64_with(&engine::STANDARD)
+    }
+
+    fn to_base64_with(&self, engine: &Engine) -> String;
+}
+
+/// Decodes a value from Base64.
+///
+/// ```
+/// use faster_base64::FromBase64;
+///
+/// assert_eq!(Vec::<u8>::from_base64("SGVsbG8=").unwrap(), b"Hello");
+/// assert_eq!(<[u8; 5]>::from_base64("SGVsbG8=").unwrap(), *b"Hello");
+/// ```
+pub trait FromBase64: Sized {
+    /// Decodes standard Base64 with padding.
+    fn from_base64(data: impl AsRef<[u8]>) -> Result<Self, DecodeError> {
+        Self::from_base64_with(data, &engine::STANDARD)
+    }
+
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError>;
+}
+
+macro_rules! impl_to_base64 {
+    ($($ty:ty),*) => {
+        $(
+            impl ToBase64 for $ty {
+                fn to_base64_with(&self, engine: &Engine) -> String {
+                    engine.encode(AsRef::<[u8]>::as_ref(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_base64!([u8], Vec<u8>, str, String, Box<[u8]>, Cow<'_, [u8]>);
+
+impl<const N: usize> ToBase64 for [u8; N] {
+    fn to_base64_with(&self, engine: &Engine) -> String {
+        engine.encode(self)
+    }
+}
+
+impl FromBase64 for Vec<u8> {
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError> {
+        engine.decode_to_vec(data.as_ref())
+    }
+}
+
+impl FromBase64 for Box<[u8]> {
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError> {
+        Vec::from_base64_with(data, engine).map(Vec::into_boxed_slice)
+    }
+}
+
+impl FromBase64 for Cow<'_, [u8]> {
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError> {
+        Vec::from_base64_with(data, engine).map(Cow::Owned)
+    }
+}
+
+impl FromBase64 for String {
+    /// Fails with [`DecodeError::InvalidUtf8`] if the decoded data is not UTF-8.
+    ///
+    /// The standard engine validates while decoding, see
+    /// [`decode_to_string`](crate::decode_to_string).
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError> {
+        if *engine == engine::STANDARD {
+            return crate::decode_to_string(data.as_ref());
+        }
+        String::from_utf8(Vec::from_base64_with(data, engine)?)
+            .map_err(|err| DecodeError::InvalidUtf8(err.utf8_error().valid_up_to()))
+    }
+}
+
+impl<const N: usize> FromBase64 for [u8; N] {
+    /// Fails unless the data decodes to exactly `N` bytes.
+    ///
+    /// The standard engine decodes straight into the array and only accepts the padded
+    /// encoding of `N` bytes, see [`decode_exact`](crate::decode_exact).
+    fn from_base64_with(data: impl AsRef<[u8]>, engine: &Engine) -> Result<Self, DecodeError> {
+        let data = data.as_ref();
+        if *engine == engine::STANDARD {
+            return crate::decode_exact(data);
+        }
+        Vec::from_base64_with(data, engine)?
+            .try_into()
+            .map_err(|_| DecodeError::InvalidLength(data.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::engine;
+    use crate::{DecodeError, FromBase64, ToBase64};
+
+    #[test]
+    fn to_base64() {
+        assert_eq!(b"Hello"[..].to_base64(), "SGVsbG8=");
+        assert_eq!(b"Hello".to_vec().to_base64(), "SGVsbG8=");
+        assert_eq!("Hello".to_string().to_base64(), "SGVsbG8=");
+        assert_eq!(Cow::Borrowed(&b"Hello"[..]).to_base64(), "SGVsbG8=");
+    }
+
+    #[test]
+    fn to_base64_with() {
+        let key: Box<[u8]> = Box::new(*b"\xfb\xff");
+        assert_eq!(key.to_base64_with(&engine::URL_SAFE_NO_PAD), "-_8");
+    }
+
+    #[test]
+    fn from_base64() {
+        assert_eq!(Box::<[u8]>::from_base64("SGk=").unwrap(), b"Hi"[..].into());
+        assert_eq!(
+            Cow::<[u8]>::from_base64_with("-_8", &engine::URL_SAFE_NO_PAD).unwrap(),
+            &b"\xfb\xff"[..]
+        );
+    }
+
+    #[test]
+    fn from_base64_string() {
+        assert_eq!(String::from_base64("SGk=").unwrap(), "Hi");
+        assert_eq!(
+            String::from_base64("SGn/"),
+            Err(DecodeError::InvalidUtf8(2))
+        );
+        assert_eq!(
+            String::from_base64_with("SGn_", &engine::URL_SAFE),
+            Err(DecodeError::InvalidUtf8(2))
+        );
+    }
+
+    #[test]
+    fn from_base64_array_length() {
+        assert_eq!(
+            <[u8; 3]>::from_base64("SGVsbG8="),
+            Err(DecodeError::InvalidLength(8))
+        );
+        assert_eq!(
+            <[u8; 4]>::from_base64("SGVsbG8="),
+            Err(DecodeError::InvalidByte(6, b'8'))
+        );
+        assert_eq!(
+            <[u8; 2]>::from_base64_with("-_8", &engine::URL_SAFE_NO_PAD),
+            Ok(*b"\xfb\xff")
+        );
+        assert_eq!(
+            <[u8; 1]>::from_base64_with("-_8", &engine::URL_SAFE_NO_PAD),
+            Err(DecodeError::InvalidLength(3))
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use crate::alphabet;
+use crate::engine::Engine;
+use crate::{padded_symbols_len, DecodeError};
+
+const INVALID: u8 = 0xff;
+
+/// Maps every symbol of `from` to the symbol of `to` with the same value.
+fn symbol_map(from: &Engine, to: &Engine) -> [u8; 256] {
+    let mut map = [INVALID; 256];
+    for (symbol, mapped) in map.iter_mut().enumerate() {
+        if let Some(value) = from.alphabet().value(symbol as u8) {
+            *mapped = to.alphabet().symbol(value);
+        }
+    }
+    map
+}
+
+/// Returns the number of symbols before the padding, checking padding against `from`.
+fn symbols_len(data: &[u8], from: &Engine) -> Result<usize, DecodeError> {
+    let len = if from.padding() {
+        padded_symbols_len(data)?
+    } else {
+        data.len()
+    };
+    if len % 4 == 1 {
+        return Err(DecodeError::InvalidLength(len));
+    }
+    Ok(len)
+}
+
+/// Returns the length of the symbols with the padding `to` needs.
+fn padded_len(symbols: usize, to: &Engine) -> usize {
+    if to.padding() {
+        symbols.next_multiple_of(4)
+    } else {
+        symbols
+    }
+}
+
+/// Returns the offset of the first symbol in `data` that has no mapping.
+fn find_unmapped(map: &[u8; 256], data: &[u8]) -> Option<usize> {
+    alphabet::find_chunked(data, |b| map[b as usize] == INVALID)
+}
+
+/// Rewrites Base64 of `from` as Base64 of `to` into `output`, without decoding it.
+///
+/// Symbols are mapped to the symbol with the same value in the other alphabet and padding
+/// is checked according to `from` and then added or removed for `to`. Returns the number of
+/// bytes written.
+///
+/// Panics if `output` is shorter than the result, `input.len() + 2` bytes are always
+/// enough.
+///
+/// ```
+/// use faster_base64::engine;
+///
+/// let mut out = [0u8; 8];
+/// let len = faster_base64::transcode(
+///     b"-_-_SGk",
+///     &engine::URL_SAFE_NO_PAD,
+///     &engine::STANDARD,
+///     &mut out,
+/// );
+/// assert_eq!(&out[..len.unwrap()], b"+/+/SGk=");
+/// ```
+pub fn transcode(
+    input: &[u8],
+    from: &Engine,
+    to: &Engine,
+    output: &mut [u8],
+) -> Result<usize, DecodeError> {
+    let symbols = symbols_len(input, from)?;
+    let len = padded_len(symbols, to);
+    assert!(output.len() >= len, "output buffer is too small");
+
+    let map = symbol_map(from, to);
+
+    if let Some(i) = find_unmapped(&map, &input[..symbols]) {
+        return Err(DecodeError::InvalidByte(i, input[i]));
+    }
+    for (out, &symbol) in output.iter_mut().zip(&input[..symbols]) {
+        *out = map[symbol as usize];
+    }
+
+    output[symbols..len].fill(b'=');
+    Ok(len)
+}
+
+/// Rewrites Base64 of `from` as Base64 of `to` in-place, like [`transcode`].
+///
+/// Returns a slice of `data` with the result. Padding can only be removed in-place, so
+/// this panics if `to` would add padding that `data` does not have room for.
+///
+/// ```
+/// use faster_base64::engine;
+///
+/// let mut data = *b"+/+/SGk=";
+/// let result =
+///     faster_base64::transcode_in_place(&mut data, &engine::STANDARD, &engine::URL_SAFE_NO_PAD);
+/// assert_eq!(result.unwrap(), b"-_-_SGk");
+/// ```
+pub fn transcode_in_place<'a>(
+    data: &'a mut [u8],
+    from: &Engine,
+    to: &Engine,
+) -> Result<&'a [u8], DecodeError> {
+    let symbols = symbols_len(data, from)?;
+    let len = padded_len(symbols, to);
+    assert!(
+        data.len() >= len,
+        "padding does not fit in-place, use `transcode` instead"
+    );
+
+    let map = symbol_map(from, to);
+
+    if let Some(i) = find_unmapped(&map, &data[..symbols]) {
+        return Err(DecodeError::InvalidByte(i, data[i]));
+    }
+    for symbol in data[..symbols].iter_mut() {
+        *symbol = map[*symbol as usize];
+    }
+
+    data[symbols..len].fill(b'=');
+    Ok(&data[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{self, Engine};
+    use crate::{transcode, transcode_in_place, DecodeError};
+
+    const ENGINES: [Engine; 4] = [
+        engine::STANDARD,
+        engine::STANDARD_NO_PAD,
+        engine::URL_SAFE,
+        engine::URL_SAFE_NO_PAD,
+    ];
+
+    #[test]
+    fn transcode_all() {
+        let data = crate::tests::data(49);
+        // Tails of 0, 1 and 2 bytes around the 64 symbols checked at once
+        for len in [0, 1, 2, 3, 47, 48, 49] {
+            for from in &ENGINES {
+                for to in &ENGINES {
+                    let input = from.encode(&data[..len]);
+                    let mut out = vec![0u8; input.len() + 2];
+                    let written = transcode(input.as_bytes(), from, to, &mut out).unwrap();
+                    assert_eq!(&out[..written], to.encode(&data[..len]).as_bytes());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transcode_in_place_all() {
+        let data = crate::tests::data(49);
+        for len in [0, 1, 2, 3, 47, 48, 49] {
+            for from in &ENGINES {
+                for to in ENGINES.iter().filter(|to| from.padding() || !to.padding()) {
+                    let mut input = from.encode(&data[..len]).into_bytes();
+                    let result = transcode_in_place(&mut input, from, to).unwrap();
+                    assert_eq!(result, to.encode(&data[..len]).as_bytes());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transcode_invalid() {
+        let mut out = [0u8; 200];
+        let mut input = engine::STANDARD.encode(&[0xfb; 120]).into_bytes();
+        assert_eq!(
+            transcode(&input, &engine::URL_SAFE, &engine::STANDARD, &mut out),
+            Err(DecodeError::InvalidByte(0, b'+'))
+        );
+        input[100] = b'-';
+        let before = input.clone();
+        assert_eq!(
+            transcode_in_place(&mut input, &engine::STANDARD, &engine::URL_SAFE),
+            Err(DecodeError::InvalidByte(100, b'-'))
+        );
+        assert_eq!(input, before);
+    }
+
+    #[test]
+    fn transcode_padding() {
+        let mut out = [0u8; 8];
+        assert_eq!(
+            transcode(b"SGk", &engine::STANDARD, &engine::URL_SAFE, &mut out),
+            Err(DecodeError::InvalidLength(3))
+        );
+        assert_eq!(
+            transcode(
+                b"SGk=",
+                &engine::STANDARD_NO_PAD,
+                &engine::URL_SAFE,
+                &mut out
+            ),
+            Err(DecodeError::InvalidByte(3, b'='))
+        );
+    }
+}
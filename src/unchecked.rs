@@ -0,0 +1,119 @@
+//! Block kernels without any checks, for callers that already uphold their preconditions.
+//!
+//! Prefer the [`kernel`](crate::kernel) functions, which check lengths and output size once
+//! per call and handle unaligned buffers.
+//!
+//! Both kernels skip the last block of their input, which callers encode or decode
+//! separately. Symbols are read and written in little-endian byte order, so a `&[u8]` of
+//! symbols can be reinterpreted as the `&[u16]` [`lcvec_dec`] takes if it is aligned to
+//! 2 bytes, and a `&mut [u8]` as the `&mut [u32]` [`lcvec_enc`] takes if it is aligned to
+//! 4 bytes, on any target.
+
+use crate::tables::{PAIR_TABLE, REVERSE_PAIR_TABLE};
+
+/// Large Chunk Vectorization decoder
+///
+/// Decodes all but the last block of standard symbols in `s`, 2 elements per block, into
+/// `out`. Symbols are not validated, invalid ones decode to unspecified bytes.
+///
+/// # Safety
+///
+/// `s` must hold at least 2 elements and `out` must have room for `(s.len() / 2) * 3` bytes.
+/// `s` and `out` must not overlap.
+pub unsafe fn lcvec_dec(s: &[u16], out: &mut [u8]) {
+    let i_max = s.len() - 2;
+    let j_max = (s.len() / 2) * 3;
+    for (i, j) in (0..i_max).step_by(2).zip((0..j_max).step_by(3)) {
+        let first = u16::from_le(*s.get_unchecked(i));
+        let second = u16::from_le(*s.get_unchecked(i + 1));
+        let value = (*REVERSE_PAIR_TABLE.get_unchecked(first as usize) as u32) << 12
+            | *REVERSE_PAIR_TABLE.get_unchecked(second as usize) as u32;
+        *out.get_unchecked_mut(j) = (value >> 16) as u8;
+        *out.get_unchecked_mut(j + 1) = (value >> 8) as u8;
+        *out.get_unchecked_mut(j + 2) = value as u8;
+    }
+}
+
+/// Large Chunk Vectorization encoder
+///
+/// Encodes all but the last block of 3 bytes in `s` into `out`, one element of 4 symbols
+/// per block.
+///
+/// # Safety
+///
+/// `s` must hold at least 3 bytes and `out` must have room for `s.len() / 3` elements.
+/// `s` and `out` must not overlap.
+pub unsafe fn lcvec_enc(s: &[u8], out: &mut [u32]) {
+    let i_max = s.len() - 3;
+    let j_max = s.len() / 3;
+    for (i, j) in (0..i_max).step_by(3).zip(0..j_max) {
+        let value = (*s.get_unchecked(i) as usize) << 16
+            | (*s.get_unchecked(i + 1) as usize) << 8
+            | *s.get_unchecked(i + 2) as usize;
+        let symbols = *PAIR_TABLE.get_unchecked(value >> 12 & 0xfff)
+            | *PAIR_TABLE.get_unchecked(value & 0xfff) << 16;
+        *out.get_unchecked_mut(j) = symbols.to_le();
+    }
+}
+
+/// Same as [`lcvec_dec`] for input that is not aligned to `u16`.
+///
+/// # Safety
+///
+/// `s` must hold at least 4 bytes and `out` must have room for `(s.len() / 4) * 3` bytes.
+pub(crate) unsafe fn lcvec_dec_unaligned(s: &[u8], out: &mut [u8]) {
+    let i_max = s.len() - 4;
+    let j_max = (s.len() / 4) * 3;
+    for (i, j) in (0..i_max).step_by(4).zip((0..j_max).step_by(3)) {
+        let pairs = (s.as_ptr().add(i) as *const [u16; 2]).read_unaligned();
+        let first = u16::from_le(pairs[0]);
+        let second = u16::from_le(pairs[1]);
+        let value = (*REVERSE_PAIR_TABLE.get_unchecked(first as usize) as u32) << 12
+            | *REVERSE_PAIR_TABLE.get_unchecked(second as usize) as u32;
+        *out.get_unchecked_mut(j) = (value >> 16) as u8;
+        *out.get_unchecked_mut(j + 1) = (value >> 8) as u8;
+        *out.get_unchecked_mut(j + 2) = value as u8;
+    }
+}
+
+/// Same as [`lcvec_dec_unaligned`], decoding in place into the start of `data`.
+///
+/// Input and output are accessed through a single pointer. Each block is written over
+/// symbols that were already read, as 3 bytes never reach past the 4 symbols they came
+/// from.
+///
+/// # Safety
+///
+/// `data` must hold at least 4 bytes.
+pub(crate) unsafe fn lcvec_dec_in_place(data: &mut [u8]) {
+    let ptr = data.as_mut_ptr();
+    let i_max = data.len() - 4;
+    for (i, j) in (0..i_max).step_by(4).zip((0..).step_by(3)) {
+        let pairs = (ptr.add(i) as *const [u16; 2]).read_unaligned();
+        let first = u16::from_le(pairs[0]);
+        let second = u16::from_le(pairs[1]);
+        let value = (*REVERSE_PAIR_TABLE.get_unchecked(first as usize) as u32) << 12
+            | *REVERSE_PAIR_TABLE.get_unchecked(second as usize) as u32;
+        *ptr.add(j) = (value >> 16) as u8;
+        *ptr.add(j + 1) = (value >> 8) as u8;
+        *ptr.add(j + 2) = value as u8;
+    }
+}
+
+/// Same as [`lcvec_enc`], for output that is not aligned to `u32`.
+///
+/// # Safety
+///
+/// `s` must hold at least 3 bytes and `out` must have room for `s.len() / 3 * 4` bytes.
+pub(crate) unsafe fn lcvec_enc_unaligned(s: &[u8], out: &mut [u8]) {
+    let i_max = s.len() - 3;
+    let j_max = s.len() / 3;
+    for (i, j) in (0..i_max).step_by(3).zip(0..j_max) {
+        let value = (*s.get_unchecked(i) as usize) << 16
+            | (*s.get_unchecked(i + 1) as usize) << 8
+            | *s.get_unchecked(i + 2) as usize;
+        let symbols = *PAIR_TABLE.get_unchecked(value >> 12 & 0xfff)
+            | *PAIR_TABLE.get_unchecked(value & 0xfff) << 16;
+        (out.as_mut_ptr().add(j * 4) as *mut u32).write_unaligned(symbols.to_le());
+    }
+}
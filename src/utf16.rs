@@ -0,0 +1,169 @@
+use crate::{
+    alphabet, decode_blocks, decode_unpadded, decoded_len, encode_to_slice, encoded_len,
+    DecodeError,
+};
+
+/// Code units narrowed or widened per chunk on the stack, a multiple of a block of both
+/// encoded (4 symbols) and decoded (3 bytes) data.
+const CHUNK: usize = 4 * 64;
+
+/// Decodes padded standard Base64 given as UTF-16 code units into `out`.
+///
+/// Code units are narrowed to bytes a chunk at a time right before decoding, code units
+/// above 0x7F are rejected with [`DecodeError::InvalidCodeUnit`]. Returns the number of
+/// bytes written.
+///
+/// Panics if `out` is shorter than the decoded data, `data.len() / 4 * 3` bytes are always
+/// enough.
+///
+/// ```
+/// let data = "SGVsbG8=".encode_utf16().collect::<Vec<_>>();
+/// let mut out = [0u8; 6];
+/// let len = faster_base64::decode_utf16(&data, &mut out).unwrap();
+/// assert_eq!(&out[..len], b"Hello");
+/// ```
+pub fn decode_utf16(data: &[u16], out: &mut [u8]) -> Result<usize, DecodeError> {
+    if !data.len().is_multiple_of(4) {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+    let padding = data
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&unit| unit == b'=' as u16)
+        .count();
+    let symbols = &data[..data.len() - padding];
+    let decoded_len = decoded_len(symbols.len());
+    assert!(out.len() >= decoded_len, "output buffer is too small");
+
+    let mut buf = [0u8; CHUNK];
+    let mut len = 0;
+
+    for (i, chunk) in symbols.chunks(CHUNK).enumerate() {
+        let narrow = &mut buf[..chunk.len()];
+        if let Some(j) = narrow_invalid(chunk, narrow) {
+            return Err(match chunk[j] {
+                0x80.. => DecodeError::InvalidCodeUnit(i * CHUNK + j, chunk[j]),
+                _ => DecodeError::InvalidByte(i * CHUNK + j, narrow[j]),
+            });
+        }
+
+        if narrow.len() == CHUNK {
+            decode_blocks(narrow, &mut out[len..]);
+            len += CHUNK / 4 * 3;
+        } else {
+            if narrow.len() % 4 == 1 {
+                return Err(DecodeError::InvalidLength(symbols.len()));
+            }
+            let decoded = decode_unpadded(narrow);
+            out[len..len + decoded.len()].copy_from_slice(decoded);
+            len += decoded.len();
+        }
+    }
+
+    Ok(len)
+}
+
+/// Narrows `units` into `narrow` and returns the offset of the first one outside of the
+/// standard alphabet.
+///
+/// Narrowing and classifying happen in the same pass, 64 code units at a time like
+/// [`alphabet::find_chunked`].
+fn narrow_invalid(units: &[u16], narrow: &mut [u8]) -> Option<usize> {
+    let chunks = units.chunks(64).zip(narrow.chunks_mut(64));
+    chunks.enumerate().find_map(|(i, (units, narrow))| {
+        let mask = units
+            .iter()
+            .zip(narrow)
+            .enumerate()
+            .fold(0, |mask, (j, (&unit, byte))| {
+                // Saturating keeps code units above 0x7F outside of the alphabet
+                *byte = unit.min(0xff) as u8;
+                mask | (alphabet::STANDARD.value(*byte).is_none() as u64) << j
+            });
+        (mask != 0).then(|| i * 64 + mask.trailing_zeros() as usize)
+    })
+}
+
+/// Encodes `data` as padded standard Base64 into `out` as UTF-16 code units.
+///
+/// Returns the number of code units written, which is `encoded_len(data.len())`.
+///
+/// Panics if `out` is shorter than that.
+///
+/// ```
+/// let mut out = [0u16; 8];
+/// let len = faster_base64::encode_utf16(b"Hello", &mut out);
+/// assert_eq!(String::from_utf16(&out[..len]).unwrap(), "SGVsbG8=");
+/// ```
+pub fn encode_utf16(data: &[u8], out: &mut [u16]) -> usize {
+    let out_size = encoded_len(data.len());
+    assert!(out.len() >= out_size, "output buffer is too small");
+
+    let mut buf = [0u8; CHUNK];
+    for (chunk, out) in data.chunks(CHUNK / 4 * 3).zip(out.chunks_mut(CHUNK)) {
+        let len = encode_to_slice(chunk, &mut buf);
+        for (unit, &byte) in out.iter_mut().zip(&buf[..len]) {
+            *unit = byte as u16;
+        }
+    }
+
+    out_size
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode_utf16, encode_utf16, DecodeError};
+
+    #[test]
+    fn encode() {
+        let data = crate::tests::data(385);
+        // Tails of 0, 1 and 2 bytes around chunks of CHUNK symbols
+        for len in [0, 1, 2, 3, 191, 192, 193, 384, 385] {
+            let mut out = vec![0u16; crate::encoded_len(len)];
+            assert_eq!(encode_utf16(&data[..len], &mut out), out.len());
+            assert_eq!(
+                String::from_utf16(&out).unwrap(),
+                crate::encode(&data[..len])
+            );
+        }
+    }
+
+    #[test]
+    fn decode() {
+        let data = crate::tests::data(385);
+        for len in [0, 1, 2, 3, 191, 192, 193, 384, 385] {
+            let encoded = crate::encode(&data[..len])
+                .encode_utf16()
+                .collect::<Vec<_>>();
+            let mut out = vec![0u8; encoded.len() / 4 * 3];
+            let written = decode_utf16(&encoded, &mut out).unwrap();
+            assert_eq!(&out[..written], &data[..len]);
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut out = [0u8; 300];
+        let mut encoded = crate::encode(&[0; 300]).encode_utf16().collect::<Vec<_>>();
+        encoded[300] = 0x0141;
+        assert_eq!(
+            decode_utf16(&encoded, &mut out),
+            Err(DecodeError::InvalidCodeUnit(300, 0x0141))
+        );
+        encoded[300] = 0x00e9;
+        assert_eq!(
+            decode_utf16(&encoded, &mut out),
+            Err(DecodeError::InvalidCodeUnit(300, 0x00e9))
+        );
+        encoded[290] = b'*' as u16;
+        assert_eq!(
+            decode_utf16(&encoded, &mut out),
+            Err(DecodeError::InvalidByte(290, b'*'))
+        );
+        assert_eq!(
+            decode_utf16(&encoded[..3], &mut out),
+            Err(DecodeError::InvalidLength(3))
+        );
+    }
+}
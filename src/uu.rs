@@ -0,0 +1,176 @@
+//! uuencode, the line-oriented format with `begin <mode> <name>` / `end` framing.
+//!
+//! Each line starts with a symbol holding the number of bytes on the line (at most 45),
+//! followed by the bytes packed 3-to-4 like Base64, without padding.
+
+use crate::alphabet::{self, Alphabet};
+use crate::tables::TABLE;
+use crate::{encode_to_slice, encoded_len, DecodeError};
+
+/// uuencode alphabet, a space is accepted in place of the backtick.
+pub const ALPHABET: Alphabet =
+    Alphabet::new(b"`!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_")
+        .alias(b' ', b'`');
+
+/// Number of bytes encoded on a full line.
+pub const LINE_LEN: usize = 45;
+
+/// Decoded contents of a `begin`/`end` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// Unix permission bits from the `begin` line.
+    pub mode: u32,
+    /// File name from the `begin` line.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Maps standard Base64 symbols (and padding) to symbols of `alphabet`.
+const fn translation(alphabet: &Alphabet) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[alphabet::STANDARD.symbol(i) as usize] = alphabet.symbol(i);
+        i += 1;
+    }
+    table[b'=' as usize] = alphabet.symbol(0);
+    table
+}
+
+pub(crate) fn encode_with(alphabet: &Alphabet, data: &[u8], mode: u32, name: &str) -> String {
+    let table = translation(alphabet);
+    let lines = data.len().div_ceil(LINE_LEN);
+    let mut out = Vec::with_capacity(encoded_len(data.len()) + lines * 2 + name.len() + 24);
+    out.extend_from_slice(format!("begin {:o} {}\n", mode, name).as_bytes());
+
+    for line in data.chunks(LINE_LEN) {
+        out.push(alphabet.symbol(line.len() as u8));
+
+        let start = out.len();
+        out.resize(start + encoded_len(line.len()), 0);
+        encode_to_slice(line, &mut out[start..]);
+        for symbol in &mut out[start..] {
+            *symbol = table[*symbol as usize];
+        }
+
+        out.push(b'\n');
+    }
+
+    out.push(alphabet.symbol(0));
+    out.extend_from_slice(b"\nend\n");
+
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+pub(crate) fn decode_with(alphabet: &Alphabet, input: &[u8]) -> Result<File, DecodeError> {
+    let mut lines = input.split(|&b| b == b'\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len() + 1;
+        Some((start, line.strip_suffix(b"\r").unwrap_or(line)))
+    });
+
+    let (mode, name) = lines
+        .by_ref()
+        .find_map(|(_, line)| parse_begin(line))
+        .ok_or(DecodeError::InvalidFraming)?;
+
+    let mut data = Vec::new();
+    let mut chunk = Vec::with_capacity(encoded_len(LINE_LEN));
+
+    for (offset, line) in lines {
+        if line == b"end" {
+            return Ok(File { mode, name, data });
+        }
+
+        let Some((&len_symbol, symbols)) = line.split_first() else {
+            continue;
+        };
+        let len = alphabet
+            .value(len_symbol)
+            .ok_or(DecodeError::InvalidByte(offset, len_symbol))? as usize;
+
+        // Some encoders strip trailing spaces, missing symbols are zero
+        chunk.clear();
+        chunk.resize(encoded_len(len), TABLE[0]);
+        for (i, (out, &symbol)) in chunk.iter_mut().zip(symbols).enumerate() {
+            let value = alphabet
+                .value(symbol)
+                .ok_or(DecodeError::InvalidByte(offset + 1 + i, symbol))?;
+            *out = TABLE[value as usize];
+        }
+
+        data.extend_from_slice(&crate::decode(&mut chunk)[..len]);
+    }
+
+    Err(DecodeError::InvalidFraming)
+}
+
+fn parse_begin(line: &[u8]) -> Option<(u32, String)> {
+    let line = std::str::from_utf8(line.strip_prefix(b"begin ")?).ok()?;
+    let (mode, name) = line.split_once(' ')?;
+    let mode = u32::from_str_radix(mode, 8).ok()?;
+    Some((mode, name.to_string()))
+}
+
+/// Encodes `data` as a uuencoded file named `name` with permission bits `mode`.
+pub fn encode(data: &[u8], mode: u32, name: &str) -> String {
+    encode_with(&ALPHABET, data, mode, name)
+}
+
+/// Decodes the first `begin`/`end` section in `input`, text before `begin` is ignored.
+pub fn decode(input: &[u8]) -> Result<File, DecodeError> {
+    decode_with(&ALPHABET, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::uu;
+    use crate::DecodeError;
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            uu::encode(b"Cat", 0o644, "cat.txt"),
+            "begin 644 cat.txt\n#0V%T\n`\nend\n"
+        );
+    }
+
+    #[test]
+    fn encode_padding() {
+        assert_eq!(
+            uu::encode(b"Hi", 0o600, "a"),
+            "begin 600 a\n\"2&D`\n`\nend\n"
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let file = uu::decode(b"junk\r\nbegin 644 cat.txt\r\n#0V%T\r\n`\r\nend\r\n").unwrap();
+        assert_eq!(file.mode, 0o644);
+        assert_eq!(file.name, "cat.txt");
+        assert_eq!(file.data, b"Cat");
+    }
+
+    #[test]
+    fn roundtrip_long() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoded = uu::encode(&data, 0o644, "bytes.bin");
+        assert_eq!(uu::decode(encoded.as_bytes()).unwrap().data, data);
+    }
+
+    #[test]
+    fn decode_missing_end() {
+        assert_eq!(
+            uu::decode(b"begin 644 cat.txt\n#0V%T\n"),
+            Err(DecodeError::InvalidFraming)
+        );
+    }
+
+    #[test]
+    fn decode_invalid_byte() {
+        assert_eq!(
+            uu::decode(b"begin 644 a\n#0v%T\n`\nend\n"),
+            Err(DecodeError::InvalidByte(14, b'v'))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Parser combinators for Base64 fields in `winnow` parsers.
+//!
+//! The parser consumes the longest run of symbols at the start of the input, followed by
+//! padding if the engine pads, and returns the decoded bytes. On partial input, such as
+//! `Partial<&[u8]>`, a run that reaches the end of the input is incomplete.
+//!
+//! ```
+//! use faster_base64::engine;
+//! use faster_base64::winnow::base64_bytes;
+//! use winnow::error::ContextError;
+//! use winnow::Parser;
+//!
+//! let mut field = ("key=", base64_bytes::<_, ContextError>(&engine::STANDARD));
+//! assert_eq!(field.parse_peek(&b"key=SGk=;"[..]).unwrap().1 .1, b"Hi");
+//! ```
+
+use ::winnow::error::{FromExternalError, Needed, ParserError};
+use ::winnow::stream::{Stream, StreamIsPartial};
+use ::winnow::Parser;
+
+use crate::engine::{Engine, Run};
+use crate::DecodeError;
+
+/// Parses a Base64 run of `engine` and returns the decoded bytes.
+///
+/// Invalid runs fail with an error built from the [`DecodeError`] through
+/// `FromExternalError`.
+pub fn base64_bytes<'a, I, E>(engine: &'static Engine) -> impl Parser<I, Vec<u8>, E>
+where
+    I: Stream<Slice = &'a [u8]> + StreamIsPartial + Clone,
+    E: ParserError<I> + FromExternalError<I, DecodeError>,
+{
+    move |input: &mut I| {
+        let data = input.peek_finish();
+
+        let len = match engine
+            .run_len(data, input.is_partial())
+            .map_err(|err| E::from_external_error(input, err))?
+        {
+            Run::Complete(len) => len,
+            Run::Incomplete(None) => return Err(E::incomplete(input, Needed::Unknown)),
+            Run::Incomplete(Some(needed)) => return Err(E::incomplete(input, Needed::new(needed))),
+        };
+        let decoded = engine
+            .decode_to_vec(&data[..len])
+            .map_err(|err| E::from_external_error(input, err))?;
+
+        input.next_slice(len);
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::winnow::error::{ContextError, ErrMode, ModalResult, Needed};
+    use ::winnow::{Parser, Partial};
+
+    use crate::engine;
+    use crate::winnow::base64_bytes;
+
+    #[test]
+    fn complete() {
+        let mut input = &b"SGVsbG8= rest"[..];
+        let result: ModalResult<_> = base64_bytes(&engine::STANDARD).parse_next(&mut input);
+        assert_eq!(result, Ok(b"Hello".to_vec()));
+        assert_eq!(input, b" rest");
+    }
+
+    #[test]
+    fn complete_missing_padding() {
+        let mut input = &b"SGVsbG8"[..];
+        let result: ModalResult<_> = base64_bytes(&engine::STANDARD).parse_next(&mut input);
+        assert!(matches!(result, Err(ErrMode::Backtrack(_))));
+    }
+
+    #[test]
+    fn partial() {
+        let mut input = Partial::new(&b"SGVsbA="[..]);
+        let result: ModalResult<_> = base64_bytes(&engine::STANDARD).parse_next(&mut input);
+        assert_eq!(result, Err(ErrMode::Incomplete(Needed::new(1))));
+
+        let mut input = Partial::new(&b"-_8"[..]);
+        let result: ModalResult<_> = base64_bytes(&engine::URL_SAFE_NO_PAD).parse_next(&mut input);
+        assert_eq!(result, Err(ErrMode::Incomplete(Needed::Unknown)));
+
+        let mut input = Partial::new(&b"-_8."[..]);
+        let result: Result<_, ErrMode<ContextError>> =
+            base64_bytes(&engine::URL_SAFE_NO_PAD).parse_next(&mut input);
+        assert_eq!(result, Ok(b"\xfb\xff".to_vec()));
+    }
+}
@@ -0,0 +1,42 @@
+//! xxencode, uuencode framing and line layout with an alphabet that survives EBCDIC
+//! translation.
+
+use crate::alphabet::Alphabet;
+use crate::uu;
+use crate::DecodeError;
+
+pub use crate::uu::{File, LINE_LEN};
+
+/// xxencode alphabet.
+pub const ALPHABET: Alphabet =
+    Alphabet::new(b"+-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+/// Encodes `data` as an xxencoded file named `name` with permission bits `mode`.
+pub fn encode(data: &[u8], mode: u32, name: &str) -> String {
+    uu::encode_with(&ALPHABET, data, mode, name)
+}
+
+/// Decodes the first `begin`/`end` section in `input`, text before `begin` is ignored.
+pub fn decode(input: &[u8]) -> Result<File, DecodeError> {
+    uu::decode_with(&ALPHABET, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xx;
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            xx::encode(b"Cat", 0o644, "cat.txt"),
+            "begin 644 cat.txt\n1Eq3o\n+\nend\n"
+        );
+    }
+
+    #[test]
+    fn roundtrip_long() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoded = xx::encode(&data, 0o644, "bytes.bin");
+        assert_eq!(xx::decode(encoded.as_bytes()).unwrap().data, data);
+    }
+}
@@ -0,0 +1,85 @@
+//! Decoding and encoding of secrets that wipes every copy of the data left behind.
+//!
+//! Owned output is returned as [`Zeroizing`], which wipes it on drop. With this feature the
+//! buffers of [`Encoder`](crate::Encoder), [`Decoder`](crate::Decoder), the iterators of
+//! [`encode_iter`](crate::encode_iter) and [`decode_iter`](crate::decode_iter) and the
+//! [`stream`](crate::stream) adapters are also wiped on drop.
+//!
+//! ```
+//! let mut data = b"c2VjcmV0IGtleQ==".to_vec();
+//! let key = faster_base64::zeroize::decode(&mut data).unwrap();
+//! assert_eq!(key, b"secret key");
+//! assert_eq!(&data[10..], [0; 6]);
+//! ```
+
+use ::zeroize::{Zeroize, Zeroizing};
+
+use crate::{engine, DecodeError};
+
+/// Decodes padded standard Base64 in-place and wipes the consumed input after the decoded
+/// data.
+///
+/// Returns a slice of `data` with decoded data. On error, all of `data` is wiped.
+pub fn decode(data: &mut [u8]) -> Result<&[u8], DecodeError> {
+    let len = match engine::STANDARD.decode(data) {
+        Ok(decoded) => decoded.len(),
+        Err(err) => {
+            data.zeroize();
+            return Err(err);
+        }
+    };
+    data[len..].zeroize();
+    Ok(&data[..len])
+}
+
+/// Decodes padded standard Base64 into a new `Vec` that is wiped on drop.
+pub fn decode_to_vec(data: &[u8]) -> Result<Zeroizing<Vec<u8>>, DecodeError> {
+    let mut out = Zeroizing::new(data.to_vec());
+    let len = decode(&mut out)?.len();
+    out.truncate(len);
+    Ok(out)
+}
+
+/// Decodes padded standard Base64 into a new `String` that is wiped on drop, see
+/// [`decode_to_str`](crate::decode_to_str).
+pub fn decode_to_string(data: &[u8]) -> Result<Zeroizing<String>, DecodeError> {
+    let mut out = Zeroizing::new(data.to_vec());
+    let len = crate::decode_to_str(&mut out)?.len();
+    out[len..].zeroize();
+    out.truncate(len);
+    let out = std::mem::take(&mut *out);
+    Ok(Zeroizing::new(unsafe { String::from_utf8_unchecked(out) }))
+}
+
+/// Encodes `data` as padded standard Base64 into a new `String` that is wiped on drop.
+pub fn encode(data: &[u8]) -> Zeroizing<String> {
+    Zeroizing::new(crate::encode(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zeroize::{decode, decode_to_string, decode_to_vec, encode};
+    use crate::DecodeError;
+
+    #[test]
+    fn decode_wipes_input() {
+        let mut data = b"SGVsbG8sIHdvcmxkIQ==".to_vec();
+        assert_eq!(decode(&mut data), Ok(&b"Hello, world!"[..]));
+        assert_eq!(&data[13..], [0; 7]);
+
+        let mut data = b"SGVsbG8*".to_vec();
+        assert_eq!(decode(&mut data), Err(DecodeError::InvalidByte(7, b'*')));
+        assert_eq!(data, [0; 8]);
+    }
+
+    #[test]
+    fn owned() {
+        assert_eq!(encode(b"Hello").as_str(), "SGVsbG8=");
+        assert_eq!(decode_to_vec(b"SGVsbG8=").unwrap().as_slice(), b"Hello");
+        assert_eq!(decode_to_string(b"SGVsbG8=").unwrap().as_str(), "Hello");
+        assert_eq!(
+            decode_to_string(b"/w==").map(|_| ()),
+            Err(DecodeError::InvalidUtf8(0))
+        );
+    }
+}
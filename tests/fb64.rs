@@ -0,0 +1,137 @@
+//! Compares `fb64` with the output of GNU `base64` 9.1 for the same flags.
+//!
+//! The references were recorded with `base64`, and with `basenc --base64url` for `--url`.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Flags, input, the reference output and whether the reference succeeded.
+type Case = (&'static [&'static str], &'static [u8], &'static [u8], bool);
+
+const CASES: &[Case] = &[
+    (&[], b"The quick brown fox jumps over the lazy dog, then wraps onto a second line of Base64.\n", b"VGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIHRoZSBsYXp5IGRvZywgdGhlbiB3cmFwcyBv\nbnRvIGEgc2Vjb25kIGxpbmUgb2YgQmFzZTY0Lgo=\n", true),
+    (&["-w", "0"], b"The quick brown fox jumps over the lazy dog, then wraps onto a second line of Base64.\n", b"VGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIHRoZSBsYXp5IGRvZywgdGhlbiB3cmFwcyBvbnRvIGEgc2Vjb25kIGxpbmUgb2YgQmFzZTY0Lgo=", true),
+    (&["-w0"], b"The quick brown fox jumps over the lazy dog, then wraps onto a second line of Base64.\n", b"VGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIHRoZSBsYXp5IGRvZywgdGhlbiB3cmFwcyBvbnRvIGEgc2Vjb25kIGxpbmUgb2YgQmFzZTY0Lgo=", true),
+    (&["--wrap=16"], b"The quick brown fox jumps over the lazy dog, then wraps onto a second line of Base64.\n", b"VGhlIHF1aWNrIGJy\nb3duIGZveCBqdW1w\ncyBvdmVyIHRoZSBs\nYXp5IGRvZywgdGhl\nbiB3cmFwcyBvbnRv\nIGEgc2Vjb25kIGxp\nbmUgb2YgQmFzZTY0\nLgo=\n", true),
+    (&["--wrap", "4"], b"abc", b"YWJj\n", true),
+    (&["--wr=5"], b"abcdefgh", b"YWJjZ\nGVmZ2\ng=\n", true),
+    (&["--wr", "0"], b"abc", b"YWJj", true),
+    (&[], b"", b"", true),
+    (&[], b"\xfb\xff\xbf\x00>?", b"+/+/AD4/\n", true),
+    (&["--url"], b"\xfb\xff\xbf\x00>?", b"-_-_AD4_\n", true),
+    (&["-d"], b"VGhlIHF1aWNr\nIGJyb3du\n", b"The quick brown", true),
+    (&["--decode"], b"YQ==\nYg==\n", b"ab", true),
+    (&["-d"], b"YQ==YQ==YWJj", b"aaabc", true),
+    (&["-d"], b"+/+/\n", b"\xfb\xff\xbf", true),
+    (&["-d", "--url"], b"-_-_\n", b"\xfb\xff\xbf", true),
+    (&["-d"], b"-_-_\n", b"", false),
+    (&["-d"], b"YQ==\r\n", b"a", false),
+    (&["-d", "-i"], b"YQ==\r\n", b"a", true),
+    (&["-di"], b"YW*Jj", b"abc", true),
+    (&["-d", "--ignore-garbage"], b"Y W\tJ j\n", b"abc", true),
+    (&["--dec", "--ig"], b"YW*Jj", b"abc", true),
+    (&["-d"], b"YW*Jj", b"a", false),
+    (&["-d"], b"YQ", b"a", false),
+    (&["-d"], b"YWJjZA=", b"abcd", false),
+    (&["-d"], b"YQ==Y===", b"a", false),
+    (&["-d"], b"Y", b"", false),
+    (&["-d"], b"", b"", true),
+];
+
+fn fb64(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fb64"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn gnu_reference() {
+    for &(args, input, expected, success) in CASES {
+        let output = fb64(args, input);
+        let input = String::from_utf8_lossy(input);
+        assert_eq!(output.stdout, expected, "{:?} {:?}", args, input);
+        assert_eq!(output.status.success(), success, "{:?} {:?}", args, input);
+        let stderr: &[u8] = if success {
+            b""
+        } else {
+            b"fb64: invalid input\n"
+        };
+        assert_eq!(output.stderr, stderr, "{:?} {:?}", args, input);
+    }
+}
+
+#[test]
+fn no_pad() {
+    // GNU `base64` always pads, so these are the padded references without padding
+    assert_eq!(fb64(&["--no-pad"], b"abcd").stdout, b"YWJjZA\n");
+    assert_eq!(fb64(&["--url", "--no-pad"], b"\xfb\xff").stdout, b"-_8\n");
+    assert_eq!(fb64(&["-d", "--no-pad"], b"YWJjZA\n").stdout, b"abcd");
+
+    let output = fb64(&["-d", "--no-pad"], b"YWJjZA==");
+    assert_eq!(output.stdout, b"abcd");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn file_operand() {
+    let path = format!("{}/fb64-input", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, "YWJj\n").unwrap();
+    assert_eq!(fb64(&["-d", &path], b"").stdout, b"abc");
+    assert_eq!(fb64(&["-d", "-"], b"ZGVm").stdout, b"def");
+
+    let output = fb64(&["-d", "missing-file"], b"");
+    assert_eq!(
+        output.stderr,
+        b"fb64: missing-file: No such file or directory\n"
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn usage_errors() {
+    // References from GNU `base64`, which only points to `--help` for misuse of options
+    let output = fb64(&["--w=x"], b"");
+    assert_eq!(output.stderr, b"fb64: invalid wrap size: 'x'\n");
+    assert!(!output.status.success());
+
+    let output = fb64(&["--dec=1"], b"");
+    assert_eq!(
+        output.stderr,
+        b"fb64: option '--decode' doesn't allow an argument\n\
+          Try 'fb64 --help' for more information.\n"
+    );
+    assert!(!output.status.success());
+
+    let output = fb64(&["--foo=1"], b"");
+    assert_eq!(
+        output.stderr,
+        b"fb64: unrecognized option '--foo=1'\n\
+          Try 'fb64 --help' for more information.\n"
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn version() {
+    let output = fb64(&["--vers"], b"");
+    assert_eq!(
+        output.stdout,
+        format!("fb64 (faster-base64) {}\n", env!("CARGO_PKG_VERSION")).as_bytes()
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn help() {
+    let output = fb64(&["--h"], b"");
+    assert!(output
+        .stdout
+        .starts_with(b"Usage: fb64 [OPTION]... [FILE]\n"));
+    assert!(output.status.success());
+}